@@ -0,0 +1,314 @@
+use std::{
+    fmt::{self, Debug},
+    ops::{Deref, DerefMut},
+    thread,
+};
+
+use smallvec::SmallVec;
+use tokio::sync::{watch, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{
+    modifier::ModifierId,
+    prelude::{Additive, Ceiling, Flat, Floor, Multiplicative, Override, Stat, StatMarker},
+    stat::breakdown::Breakdown,
+    timed::{Duration, Tick},
+};
+
+/// [`Stat`] behind an async [`RwLock`], whose guards can be held across `.await` points.
+///
+/// Every change recomputes the final value and sends it to [change streams][MiniStat::changes],
+/// if it differs from the previous one.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::prelude::*;
+///
+/// struct Speed;
+///
+/// impl StatMarker for Speed {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+/// runtime.block_on(async {
+///     let stat = MiniStatAsync::<Speed>::with_base(5.);
+///     let mut changes = stat.changes();
+///
+///     stat.apply_mul(Multiplicative::from_raw(2.)).await;
+///
+///     assert_eq!(stat.cached().await, 10.);
+///     assert_eq!(changes.next().await, Some(10.));
+/// });
+/// #   Ok(())
+/// # }
+/// ```
+pub struct MiniStat<Marker, const N: usize = 2>
+where
+    Marker: StatMarker,
+{
+    stat: RwLock<Stat<Marker, N>>,
+    changes: watch::Sender<Marker::Raw>,
+}
+
+/// Exclusive access to the stat of a [`MiniStat`], which sends the final value to
+/// [change streams][MiniStat::changes] when dropped.
+pub struct StatMut<'a, Marker, const N: usize>
+where
+    Marker: StatMarker,
+{
+    stat: RwLockWriteGuard<'a, Stat<Marker, N>>,
+    changes: &'a watch::Sender<Marker::Raw>,
+}
+
+impl<Marker, const N: usize> Deref for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    type Target = Stat<Marker, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stat
+    }
+}
+
+impl<Marker, const N: usize> DerefMut for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stat
+    }
+}
+
+impl<Marker, const N: usize> Drop for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    fn drop(&mut self) {
+        // Recomputing the value runs curves, bounds and observers, which may panic again and abort
+        // the unwinding task; change streams keep the previous value instead.
+        if thread::panicking() {
+            return;
+        }
+        let Some(new) = self.stat.cache_value().cached() else {
+            return;
+        };
+        self.changes.send_if_modified(|value| {
+            let changed = *value != new;
+            *value = new;
+            changed
+        });
+    }
+}
+
+/// Stream of the final values of a [`MiniStat`], see [`MiniStat::changes`].
+pub struct Changes<R>(watch::Receiver<R>);
+
+impl<R: Copy> Changes<R> {
+    /// Waits until the final value changes and returns it, or `None` once the stat is dropped.
+    ///
+    /// Changes happening before the task gets woken up are coalesced into the latest value.
+    pub async fn next(&mut self) -> Option<R> {
+        self.0.changed().await.ok()?;
+        Some(*self.0.borrow_and_update())
+    }
+
+    /// Latest final value, without marking it as seen.
+    pub fn current(&self) -> R {
+        *self.0.borrow()
+    }
+}
+
+impl<R: Debug> Debug for Changes<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Changes").field(&*self.0.borrow()).finish()
+    }
+}
+
+impl<Marker, const N: usize> MiniStat<Marker, N>
+where
+    Marker: StatMarker,
+{
+    pub fn new(mut stat: Stat<Marker, N>) -> Self {
+        let value = stat.cache_value().cached().expect("value was just cached");
+        Self {
+            stat: RwLock::new(stat),
+            changes: watch::Sender::new(value),
+        }
+    }
+
+    pub fn with_base(base: Marker::Raw) -> Self {
+        Self::new(Stat::<Marker, N>::with_base(base))
+    }
+
+    pub async fn stat(&self) -> RwLockReadGuard<'_, Stat<Marker, N>> {
+        self.stat.read().await
+    }
+
+    pub async fn stat_mut(&self) -> StatMut<'_, Marker, N> {
+        StatMut {
+            stat: self.stat.write().await,
+            changes: &self.changes,
+        }
+    }
+
+    /// Stream of the final values, starting after the current one.
+    pub fn changes(&self) -> Changes<Marker::Raw> {
+        Changes(self.changes.subscribe())
+    }
+
+    pub async fn base(&self) -> Marker::Raw {
+        self.stat().await.base
+    }
+
+    pub async fn set_base(&self, base: Marker::Raw) {
+        self.stat_mut().await.set_base(base);
+    }
+
+    pub async fn cached(&self) -> Marker::Raw {
+        if let Some(value) = self.stat().await.cached() {
+            return value;
+        }
+        let mut stat = self.stat_mut().await;
+        stat.cache_value();
+        stat.cached()
+            .expect("value is cached while the stat is locked")
+    }
+
+    pub async fn breakdown(&self) -> Breakdown<Marker> {
+        self.stat().await.breakdown()
+    }
+
+    pub async fn apply_flat(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_flat(flat)
+    }
+
+    pub async fn apply_add(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_add(add)
+    }
+
+    pub async fn apply_mul(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_mul(mul)
+    }
+
+    pub async fn apply_floor(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_floor(floor)
+    }
+
+    pub async fn apply_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_ceiling(ceiling)
+    }
+
+    pub async fn apply_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_override(modifier)
+    }
+
+    pub async fn apply_flat_timed(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_flat_timed(flat, duration)
+    }
+
+    pub async fn apply_add_timed(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_add_timed(add, duration)
+    }
+
+    pub async fn apply_mul_timed(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().await.apply_mul_timed(mul, duration)
+    }
+
+    pub async fn tick(&self, delta: Tick) -> SmallVec<[ModifierId; N]> {
+        self.stat_mut().await.tick(delta)
+    }
+
+    pub async fn remove_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().await.remove_flat(flat);
+    }
+
+    pub async fn remove_add(&self, additive: Additive<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().await.remove_add(additive);
+    }
+
+    pub async fn remove_mul(
+        &self,
+        multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) {
+        self.stat_mut().await.remove_mul(multiplicative);
+    }
+
+    pub async fn remove_floor(&self, floor: Floor<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().await.remove_floor(floor);
+    }
+
+    pub async fn remove_ceiling(&self, ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().await.remove_ceiling(ceiling);
+    }
+
+    pub async fn remove_override(&self, modifier: Override<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().await.remove_override(modifier);
+    }
+
+    pub async fn remove_by_id(&self, id: ModifierId) {
+        self.stat_mut().await.remove_by_id(id);
+    }
+
+    pub async fn remove_with_metadata(&self, metadata: Marker::Metadata) {
+        self.stat_mut().await.remove_with_metadata(metadata);
+    }
+
+    /// See [`Stat::batch`]. The stat stays locked for the whole batch, so change streams see only
+    /// its result.
+    pub async fn batch<T, E>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, N>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        self.stat_mut().await.batch(f)
+    }
+}
+
+impl<Marker, const N: usize> Debug for MiniStat<Marker, N>
+where
+    Marker: StatMarker + Debug,
+    <Marker as StatMarker>::Raw: Debug,
+    <Marker as StatMarker>::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stat.try_read() {
+            Ok(stat) => stat.fmt(f),
+            Err(_) => f.debug_struct("MiniStat").finish_non_exhaustive(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+/// Lower or upper bound of the final value of a [`Stat`][crate::stat::Stat].
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # #[cfg(feature = "sync")]
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::sync::Arc;
+///
+/// use mini_stat::{bound::Bound, prelude::*};
+///
+/// struct Resistance;
+///
+/// impl StatMarker for Resistance {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// struct MaxResistance;
+///
+/// impl StatMarker for MaxResistance {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let cap = Arc::new(MiniStatSync::<MaxResistance>::with_base(0.75));
+///
+/// let mut resistance = Stat::<Resistance>::with_base(0.9);
+/// resistance.set_min(Bound::Value(0.));
+/// resistance.set_max(Bound::from_fn({
+///     let cap = cap.clone();
+///     move || cap.cached()
+/// }));
+///
+/// assert_eq!(resistance.cached(), Some(0.75));
+/// assert_eq!(resistance.unclamped(), Some(0.9));
+///
+/// cap.apply_flat(Flat::from_raw(0.05));
+///
+/// assert_eq!(resistance.cached(), Some(0.8));
+/// #   Ok(())
+/// # }
+/// #
+/// # #[cfg(not(feature = "sync"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub enum Bound<R> {
+    /// Constant bound.
+    Value(R),
+    /// Bound read on each query, e.g. from another stat.
+    ///
+    /// The stat isn't told, when the bound changes: observers don't get notified until the stat is
+    /// [cached][crate::stat::Stat::cache_value] again, and a
+    /// [`Resource`][crate::resource::Resource] doesn't adjust its current value until it is
+    /// [refreshed][crate::resource::Resource::refresh] or changed.
+    Dynamic(Arc<dyn Fn() -> R + Send + Sync>),
+}
+
+impl<R: Copy> Bound<R> {
+    pub fn from_fn(f: impl Fn() -> R + Send + Sync + 'static) -> Self {
+        Self::Dynamic(Arc::new(f))
+    }
+
+    /// Current value of the bound.
+    pub fn get(&self) -> R {
+        match self {
+            Self::Value(value) => *value,
+            Self::Dynamic(f) => f(),
+        }
+    }
+}
+
+impl<R> From<R> for Bound<R> {
+    fn from(value: R) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<R: Debug> Debug for Bound<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(value) => f.debug_tuple("Value").field(value).finish(),
+            Self::Dynamic(_) => f.debug_tuple("Dynamic").finish_non_exhaustive(),
+        }
+    }
+}
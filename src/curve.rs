@@ -0,0 +1,98 @@
+use std::{
+    fmt::{self, Debug},
+    ops::{Div, Sub},
+    sync::Arc,
+};
+
+use crate::number::{StatFloat, StatNumber};
+
+/// Diminishing returns curve, applied to the total of flat or additive modifiers of each stage of
+/// a [`Stat`][crate::stat::Stat].
+///
+/// See [`Stat::set_flat_curve`][crate::stat::Stat::set_flat_curve] and
+/// [`Stat::set_additive_curve`][crate::stat::Stat::set_additive_curve].
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::curve::Curve;
+///
+/// let hyperbolic = Curve::hyperbolic(100.);
+/// assert_eq!(hyperbolic.apply(100.), 0.5);
+/// assert_eq!(hyperbolic.apply(300.), 0.75);
+///
+/// let piecewise = Curve::piecewise_linear([(0., 0.), (10., 10.), (30., 20.)]);
+/// assert_eq!(piecewise.apply(5.), 5.);
+/// assert_eq!(piecewise.apply(20.), 15.);
+/// assert_eq!(piecewise.apply(50.), 20.);
+///
+/// let custom = Curve::from_fn(|x: f32| x.sqrt());
+/// assert_eq!(custom.apply(16.), 4.);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Curve<R>(Arc<dyn Fn(R) -> R + Send + Sync>);
+
+impl<R> Curve<R> {
+    pub fn from_fn(f: impl Fn(R) -> R + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Maps the total `x` of modifiers.
+    pub fn apply(&self, x: R) -> R {
+        (self.0)(x)
+    }
+}
+
+impl<R> Curve<R>
+where
+    R: StatNumber + Sub<Output = R> + Div<Output = R> + Send + Sync + 'static,
+{
+    /// Linear interpolation between `points` sorted by `x`. Values outside of the points are
+    /// clamped to the first or the last point.
+    ///
+    /// # Panics
+    /// Panics if `points` are empty.
+    pub fn piecewise_linear(points: impl Into<Vec<(R, R)>>) -> Self {
+        let points = points.into();
+        assert!(!points.is_empty(), "piecewise linear curve needs points");
+        Self::from_fn(move |x| {
+            let (x0, y0) = points[0];
+            if x <= x0 {
+                return y0;
+            }
+            for window in points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (window[0], window[1]);
+                if x <= x1 {
+                    return y0 + (x - x0) * (y1 - y0) / (x1 - x0);
+                }
+            }
+            points[points.len() - 1].1
+        })
+    }
+}
+
+impl<R> Curve<R>
+where
+    R: StatFloat + Send + Sync + 'static,
+{
+    /// `x / (x + k)`, approaching 1. Reaches one half at `x = k`.
+    pub fn hyperbolic(k: R) -> Self {
+        Self::from_fn(move |x| x / (x + k))
+    }
+
+    /// `1 - e^(-x / k)`, approaching 1. The limit of `1 - Π(1 - r)` as the stacks `r`, summing up
+    /// to `x / k`, get smaller and more numerous; any finite number of stacks gives slightly less.
+    pub fn exponential(k: R) -> Self {
+        Self::from_fn(move |x| R::ONE - (R::ZERO - x / k).exp())
+    }
+}
+
+impl<R> Debug for Curve<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Curve").finish_non_exhaustive()
+    }
+}
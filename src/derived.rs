@@ -0,0 +1,566 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display},
+    ops::Sub,
+    sync::Arc,
+};
+
+use smallvec::SmallVec;
+
+use crate::{
+    bound::Bound,
+    modifier::ModifierId,
+    number::StatNumber,
+    prelude::{Additive, Flat, Modifier, Multiplicative, Stat, StatMarker},
+    stat::erased::ErasedStat,
+};
+
+/// Error returned when registering a derivation in a [`StatGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// Stat with given marker wasn't inserted into the graph.
+    MissingStat(&'static str),
+    /// Derivation would make a stat depend on itself. Contains the offending dependency path.
+    Cycle(Vec<&'static str>),
+    /// Conversions from the stat would add up to more than 100%.
+    Overconverted(&'static str),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStat(name) => write!(f, "stat `{name}` is not in the graph"),
+            Self::Cycle(path) => write!(f, "dependency cycle: {}", path.join(" -> ")),
+            Self::Overconverted(name) => write!(f, "conversions of `{name}` exceed 100%"),
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+/// Stat a derivation depends on.
+///
+/// See [`StatGraph::derive_base`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Source {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl Source {
+    /// Source for the stat marked with `Marker`.
+    pub fn of<Marker: StatMarker + 'static>() -> Self {
+        Self {
+            id: TypeId::of::<Marker>(),
+            name: type_name::<Marker>(),
+        }
+    }
+}
+
+/// Final values of the [sources][Source] of a derivation.
+pub struct Sources<'a, const N: usize> {
+    nodes: &'a HashMap<TypeId, Node<N>>,
+    allowed: &'a [Source],
+}
+
+impl<const N: usize> Sources<'_, N> {
+    /// Final value of the source stat marked with `Marker`.
+    ///
+    /// # Panics
+    ///
+    /// If `Marker` isn't one of the sources declared for the derivation.
+    pub fn value<Marker: StatMarker + 'static>(&self) -> Marker::Raw {
+        self.stat::<Marker>()
+            .cached()
+            .expect("sources are resolved before derivation")
+    }
+
+    /// Final value of the source stat marked with `Marker`, before any part of it was
+    /// [converted][StatGraph::convert] to other stats.
+    ///
+    /// # Panics
+    ///
+    /// If `Marker` isn't one of the sources declared for the derivation.
+    pub fn unconverted<Marker: StatMarker + 'static>(&self) -> Marker::Raw {
+        self.stat::<Marker>()
+            .unconverted()
+            .expect("sources are resolved before derivation")
+    }
+
+    fn stat<Marker: StatMarker + 'static>(&self) -> &Stat<Marker, N> {
+        let source = Source::of::<Marker>();
+        assert!(
+            self.allowed.contains(&source),
+            "`{}` is not a source of the derivation",
+            source.name
+        );
+        self.nodes
+            .get(&source.id)
+            .and_then(|node| node.stat.as_any().downcast_ref())
+            .expect("sources are in the graph")
+    }
+}
+
+type Apply<const N: usize> = Box<dyn FnMut(&Sources<'_, N>, &mut dyn Any)>;
+
+struct Derivation<const N: usize> {
+    sources: SmallVec<[Source; 4]>,
+    apply: Apply<N>,
+}
+
+struct Node<const N: usize> {
+    name: &'static str,
+    stat: Box<dyn ErasedStat>,
+    derivations: Vec<Derivation<N>>,
+    dependents: SmallVec<[TypeId; 4]>,
+    dirty: bool,
+}
+
+/// Container of stats, whose base values or modifiers are derived from other stats.
+///
+/// Changing a stat through [`StatGraph::update`] invalidates every stat derived from it, which
+/// gets recomputed on the next [`StatGraph::value`] query.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::{derived::{Source, StatGraph}, prelude::*};
+///
+/// struct Constitution;
+///
+/// impl StatMarker for Constitution {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// struct MaxHealth;
+///
+/// impl StatMarker for MaxHealth {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let mut graph = StatGraph::<2>::new();
+/// graph
+///     .insert(Stat::<Constitution>::with_base(5.))
+///     .insert(Stat::<MaxHealth>::with_base(0.));
+///
+/// graph.derive_base::<MaxHealth>(&[Source::of::<Constitution>()], |s| {
+///     10. * s.value::<Constitution>()
+/// })?;
+///
+/// assert_eq!(graph.value::<MaxHealth>(), Some(50.));
+///
+/// graph.update::<Constitution, _>(|stat| stat.apply_flat(Flat::from_raw(1.)));
+///
+/// assert_eq!(graph.value::<MaxHealth>(), Some(60.));
+/// #   Ok(())
+/// # }
+/// ```
+pub struct StatGraph<const N: usize = 2> {
+    nodes: HashMap<TypeId, Node<N>>,
+    /// Percents converted from each stat, as `Vec<Marker::Raw>`.
+    conversions: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl<const N: usize> Default for StatGraph<N> {
+    fn default() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            conversions: HashMap::new(),
+        }
+    }
+}
+
+impl<const N: usize> StatGraph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a stat into the graph, replacing the previous stat with the same marker.
+    ///
+    /// Derivations and [conversions][StatGraph::convert] of the replaced stat are kept.
+    pub fn insert<Marker>(&mut self, mut stat: Stat<Marker, N>) -> &mut Self
+    where
+        Marker: StatMarker + 'static,
+    {
+        let id = TypeId::of::<Marker>();
+        match self.nodes.get_mut(&id) {
+            Some(node) => {
+                if let Some(old) = node.stat.as_any().downcast_ref::<Stat<Marker, N>>() {
+                    stat.conversion = old.conversion.clone();
+                }
+                node.stat = Box::new(stat);
+                node.dirty = !node.derivations.is_empty();
+                self.invalidate_dependents(id);
+            }
+            None => {
+                self.nodes.insert(
+                    id,
+                    Node {
+                        name: type_name::<Marker>(),
+                        stat: Box::new(stat),
+                        derivations: Vec::new(),
+                        dependents: SmallVec::new(),
+                        dirty: false,
+                    },
+                );
+            }
+        }
+        self
+    }
+
+    /// Gets the stat marked with `Marker`.
+    ///
+    /// Cached value of a derived stat may be missing, use [`StatGraph::value`] to recompute it.
+    pub fn get<Marker>(&self) -> Option<&Stat<Marker, N>>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.nodes
+            .get(&TypeId::of::<Marker>())
+            .and_then(|node| node.stat.as_any().downcast_ref())
+    }
+
+    /// Modifies the stat marked with `Marker` and invalidates all stats derived from it.
+    pub fn update<Marker, R>(&mut self, f: impl FnOnce(&mut Stat<Marker, N>) -> R) -> Option<R>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let id = TypeId::of::<Marker>();
+        let result = self
+            .nodes
+            .get_mut(&id)
+            .and_then(|node| node.stat.as_any_mut().downcast_mut())
+            .map(f);
+        self.invalidate_dependents(id);
+        result
+    }
+
+    /// Final value of the stat marked with `Marker`, recomputing its derivations if needed.
+    pub fn value<Marker>(&mut self) -> Option<Marker::Raw>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let id = TypeId::of::<Marker>();
+        self.resolve(id);
+        self.get::<Marker>().and_then(Stat::cached)
+    }
+
+    /// Derives base value of the stat marked with `Marker` from final values of `sources`.
+    pub fn derive_base<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Marker::Raw + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.derive::<Marker>(sources, move |sources, stat| {
+            stat.set_base(f(sources));
+        })
+    }
+
+    /// Derives [lower bound][Stat::set_min] of the stat marked with `Marker` from final values of
+    /// `sources`.
+    pub fn derive_min<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Marker::Raw + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.derive::<Marker>(sources, move |sources, stat| {
+            stat.set_min(Bound::Value(f(sources)));
+        })
+    }
+
+    /// Derives [upper bound][Stat::set_max] of the stat marked with `Marker` from final values of
+    /// `sources`.
+    pub fn derive_max<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Marker::Raw + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.derive::<Marker>(sources, move |sources, stat| {
+            stat.set_max(Bound::Value(f(sources)));
+        })
+    }
+
+    /// Derives a flat modifier of the stat marked with `Marker` from final values of `sources`.
+    pub fn derive_flat<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Flat<Marker, Marker::Raw, Marker::Metadata> + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let mut applied: Option<ModifierId> = None;
+        self.derive::<Marker>(sources, move |sources, stat| {
+            if let Some(id) = applied.take() {
+                stat.remove_by_id(id);
+            }
+            applied = Some(stat.apply_flat(f(sources)));
+        })
+    }
+
+    /// Derives an additive modifier of the stat marked with `Marker` from final values of
+    /// `sources`.
+    pub fn derive_add<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Additive<Marker, Marker::Raw, Marker::Metadata> + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let mut applied: Option<ModifierId> = None;
+        self.derive::<Marker>(sources, move |sources, stat| {
+            if let Some(id) = applied.take() {
+                stat.remove_by_id(id);
+            }
+            applied = Some(stat.apply_add(f(sources)));
+        })
+    }
+
+    /// Derives a multiplicative modifier of the stat marked with `Marker` from final values of
+    /// `sources`.
+    pub fn derive_mul<Marker>(
+        &mut self,
+        sources: &[Source],
+        f: impl Fn(&Sources<'_, N>) -> Multiplicative<Marker, Marker::Raw, Marker::Metadata> + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let mut applied: Option<ModifierId> = None;
+        self.derive::<Marker>(sources, move |sources, stat| {
+            if let Some(id) = applied.take() {
+                stat.remove_by_id(id);
+            }
+            applied = Some(stat.apply_mul(f(sources)));
+        })
+    }
+
+    /// Adds `percent` of the final value of the stat marked with `From` to the stat marked with
+    /// `To` as a flat modifier (e.g. "gain 10% of max mana as extra energy shield").
+    pub fn gain_as<From, To>(&mut self, percent: To::Raw) -> Result<&mut Self, GraphError>
+    where
+        From: StatMarker<Raw = To::Raw> + 'static,
+        To: StatMarker + 'static,
+        To::Raw: 'static,
+    {
+        self.derive_flat::<To>(&[Source::of::<From>()], move |sources| {
+            Flat::from_raw(percent * sources.value::<From>())
+        })
+    }
+
+    /// Converts `percent` of the stat marked with `From` to the stat marked with `To` (e.g. "20%
+    /// of physical damage converted to fire").
+    ///
+    /// The converted part is added to `To` as a flat modifier and subtracted from the final value
+    /// of `From`, after its floors, ceilings, overrides and bounds, so the total stays the same.
+    /// Conversions to multiple stats are based on the value before any conversion, see
+    /// [`Sources::unconverted`], and may add up to 100% at most.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::{derived::{GraphError, StatGraph}, prelude::*};
+    ///
+    /// struct Physical;
+    ///
+    /// impl StatMarker for Physical {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// struct Fire;
+    ///
+    /// impl StatMarker for Fire {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut graph = StatGraph::<2>::new();
+    /// graph
+    ///     .insert(Stat::<Physical>::with_base(100.))
+    ///     .insert(Stat::<Fire>::with_base(10.));
+    ///
+    /// graph.convert::<Physical, Fire>(0.2)?;
+    ///
+    /// assert_eq!(graph.value::<Physical>(), Some(80.));
+    /// assert_eq!(graph.value::<Fire>(), Some(30.));
+    /// assert!(matches!(graph.convert::<Fire, Physical>(0.5), Err(GraphError::Cycle(_))));
+    /// assert!(matches!(
+    ///     graph.convert::<Physical, Fire>(0.9),
+    ///     Err(GraphError::Overconverted(_))
+    /// ));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn convert<From, To>(&mut self, percent: To::Raw) -> Result<&mut Self, GraphError>
+    where
+        From: StatMarker<Raw = To::Raw> + 'static,
+        To: StatMarker + 'static,
+        To::Raw: Sub<Output = To::Raw> + Send + Sync + 'static,
+    {
+        let from = Source::of::<From>();
+        let mut percents = self
+            .conversions
+            .get(&from.id)
+            .and_then(|percents| percents.downcast_ref::<Vec<To::Raw>>())
+            .cloned()
+            .unwrap_or_default();
+        let total = percents
+            .iter()
+            .fold(percent, |total, &percent| total + percent);
+        if total > To::Raw::ONE {
+            return Err(GraphError::Overconverted(from.name));
+        }
+        self.derive_flat::<To>(&[from], move |sources| {
+            Flat::from_raw(percent * sources.unconverted::<From>())
+        })?;
+
+        percents.push(percent);
+        self.conversions.insert(from.id, Box::new(percents.clone()));
+        self.update::<From, _>(|stat| {
+            stat.set_conversion(Arc::new(move |value| {
+                percents
+                    .iter()
+                    .fold(value, |rest, &percent| rest - percent * value)
+            }));
+        });
+        Ok(self)
+    }
+
+    fn derive<Marker>(
+        &mut self,
+        sources: &[Source],
+        mut apply: impl FnMut(&Sources<'_, N>, &mut Stat<Marker, N>) + 'static,
+    ) -> Result<&mut Self, GraphError>
+    where
+        Marker: StatMarker + 'static,
+    {
+        let target = Source::of::<Marker>();
+        if !self.nodes.contains_key(&target.id) {
+            return Err(GraphError::MissingStat(target.name));
+        }
+        for source in sources {
+            if !self.nodes.contains_key(&source.id) {
+                return Err(GraphError::MissingStat(source.name));
+            }
+            if let Some(mut path) = self.path(target.id, source.id) {
+                path.push(target.name);
+                return Err(GraphError::Cycle(path));
+            }
+        }
+
+        for source in sources {
+            let dependents = &mut self.nodes.get_mut(&source.id).unwrap().dependents;
+            if !dependents.contains(&target.id) {
+                dependents.push(target.id);
+            }
+        }
+        let node = self.nodes.get_mut(&target.id).unwrap();
+        node.derivations.push(Derivation {
+            sources: sources.iter().copied().collect(),
+            apply: Box::new(move |sources, stat| {
+                apply(
+                    sources,
+                    stat.downcast_mut().expect("stat type matches marker"),
+                )
+            }),
+        });
+        node.dirty = true;
+        node.stat.invalidate();
+        self.invalidate_dependents(target.id);
+        Ok(self)
+    }
+
+    /// Finds dependency path between two stats.
+    fn path(&self, from: TypeId, to: TypeId) -> Option<Vec<&'static str>> {
+        let node = &self.nodes[&from];
+        if from == to {
+            return Some(vec![node.name]);
+        }
+        node.dependents.iter().find_map(|&next| {
+            self.path(next, to).map(|mut path| {
+                path.insert(0, node.name);
+                path
+            })
+        })
+    }
+
+    fn invalidate_dependents(&mut self, id: TypeId) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+        for dependent in node.dependents.clone() {
+            let node = self.nodes.get_mut(&dependent).unwrap();
+            if !node.dirty {
+                node.dirty = true;
+                node.stat.invalidate();
+                self.invalidate_dependents(dependent);
+            }
+        }
+    }
+
+    fn resolve(&mut self, id: TypeId) {
+        let Some(node) = self.nodes.get_mut(&id) else {
+            return;
+        };
+        if !node.dirty {
+            node.stat.cache_value();
+            return;
+        }
+
+        let sources: SmallVec<[TypeId; 4]> = node
+            .derivations
+            .iter()
+            .flat_map(|derivation| derivation.sources.iter().map(|source| source.id))
+            .collect();
+        for source in sources {
+            self.resolve(source);
+        }
+
+        let mut node = self.nodes.remove(&id).unwrap();
+        for derivation in &mut node.derivations {
+            let sources = Sources {
+                nodes: &self.nodes,
+                allowed: &derivation.sources,
+            };
+            (derivation.apply)(&sources, node.stat.as_any_mut());
+        }
+        node.stat.cache_value();
+        node.dirty = false;
+        self.nodes.insert(id, node);
+    }
+}
+
+impl<const N: usize> Debug for StatGraph<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.nodes
+                    .values()
+                    .map(|node| (node.name, node.derivations.len())),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,50 @@
+use std::{
+    cell::{BorrowError, BorrowMutError},
+    error,
+    fmt::{self, Display},
+    sync::PoisonError,
+};
+
+/// Failure of a fallible (`try_`) method of a [`MiniStat`][crate::prelude::MiniStat] or a
+/// [`MiniStatSync`][crate::prelude::MiniStatSync].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Stat is already borrowed in a conflicting way, e.g. by a held
+    /// [`stat_mut`][crate::refcell::MiniStat::stat_mut] guard, or locked by the current thread.
+    BorrowConflict,
+    /// Thread panicked while modifying the stat. See
+    /// [`clear_poison`][crate::sync::MiniStat::clear_poison].
+    Poisoned,
+    /// Final value is stale and can't be recomputed, because the stat is borrowed.
+    CacheMissing,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BorrowConflict => write!(f, "stat is already borrowed"),
+            Self::Poisoned => write!(f, "stat was poisoned by a panic"),
+            Self::CacheMissing => write!(f, "stat has no cached value"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<BorrowError> for Error {
+    fn from(_: BorrowError) -> Self {
+        Self::BorrowConflict
+    }
+}
+
+impl From<BorrowMutError> for Error {
+    fn from(_: BorrowMutError) -> Self {
+        Self::BorrowConflict
+    }
+}
+
+impl<T> From<PoisonError<T>> for Error {
+    fn from(_: PoisonError<T>) -> Self {
+        Self::Poisoned
+    }
+}
@@ -0,0 +1,150 @@
+use std::ops::Sub;
+
+use crate::{curve::Curve, number::StatNumber};
+
+/// Evaluation pipeline of a single [stage][crate::modifier::Modifier::order] of a
+/// [`Stat`][crate::stat::Stat], selected by [`StatMarker::Formula`][crate::stat::StatMarker::Formula].
+///
+/// Stages are evaluated in ascending order, each getting the result of the previous stage (or the
+/// base value) as `input`. Floors, ceilings, overrides and bounds apply to the result of the last
+/// stage regardless of the formula.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::{formula::StageModifiers, prelude::*};
+///
+/// /// Additives in percentage points (`10.` is +10%).
+/// struct PercentagePoints;
+///
+/// impl Formula<f32> for PercentagePoints {
+///     fn evaluate(input: f32, modifiers: &StageModifiers<'_, f32>) -> f32 {
+///         let percent = 1. + modifiers.additive_sum() / 100.;
+///         (input + modifiers.flat_sum()) * percent * modifiers.product()
+///     }
+/// }
+///
+/// struct Damage;
+///
+/// impl StatMarker for Damage {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = PercentagePoints;
+/// }
+///
+/// let mut stat = Stat::<Damage>::with_base(10.);
+/// stat.apply_flat(Flat::from_raw(5.));
+/// stat.apply_add(Additive::from_raw(20.));
+///
+/// assert_eq!(stat.cache_value().cached(), Some(18.));
+/// #   Ok(())
+/// # }
+/// ```
+pub trait Formula<R: StatNumber> {
+    /// Result of a stage with `modifiers`, applied to `input`.
+    fn evaluate(input: R, modifiers: &StageModifiers<'_, R>) -> R;
+}
+
+/// Raw values of the modifiers of a single stage, grouped by kind.
+///
+/// Contains only modifiers, which apply, i.e. not the ones suppressed by
+/// [stacking policies][crate::stat::Stat::set_stacking] or failing conditions.
+pub struct StageModifiers<'a, R> {
+    pub flats: &'a [R],
+    pub additives: &'a [R],
+    pub multiplicatives: &'a [R],
+    pub(crate) flat_curve: Option<&'a Curve<R>>,
+    pub(crate) additive_curve: Option<&'a Curve<R>>,
+}
+
+impl<R: StatNumber> StageModifiers<'_, R> {
+    /// Sum of the flats, mapped by the [flat curve][crate::stat::Stat::set_flat_curve]. Zero
+    /// without flats, regardless of the curve.
+    pub fn flat_sum(&self) -> R {
+        curved(self.flats, self.flat_curve)
+    }
+
+    /// Sum of the additives, mapped by the
+    /// [additive curve][crate::stat::Stat::set_additive_curve]. Zero without additives,
+    /// regardless of the curve.
+    pub fn additive_sum(&self) -> R {
+        curved(self.additives, self.additive_curve)
+    }
+
+    /// Product of the multiplicatives.
+    pub fn product(&self) -> R {
+        self.multiplicatives
+            .iter()
+            .fold(R::ONE, |acc, &raw| acc * raw)
+    }
+}
+
+/// Sum of `raws`, mapped by `curve` unless there are none.
+fn curved<R: StatNumber>(raws: &[R], curve: Option<&Curve<R>>) -> R {
+    let sum = raws.iter().fold(R::ZERO, |acc, &raw| acc + raw);
+    match curve {
+        Some(curve) if !raws.is_empty() => curve.apply(sum),
+        _ => sum,
+    }
+}
+
+/// `(input + Σflats) * (1 + Σadditives) * Πmultiplicatives`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Standard;
+
+impl<R: StatNumber> Formula<R> for Standard {
+    fn evaluate(input: R, modifiers: &StageModifiers<'_, R>) -> R {
+        (input + modifiers.flat_sum()) * (R::ONE + modifiers.additive_sum()) * modifiers.product()
+    }
+}
+
+/// Path of Exile style "increased" and "more" modifiers:
+/// `(input + Σflats) * (1 + Σadditives) * Π(1 + multiplicatives)`.
+///
+/// Additives are "increased" and multiplicatives are "more" modifiers, both as fractions (`0.2`
+/// is 20% increased or more).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IncreasedMore;
+
+impl<R: StatNumber> Formula<R> for IncreasedMore {
+    fn evaluate(input: R, modifiers: &StageModifiers<'_, R>) -> R {
+        let more = modifiers
+            .multiplicatives
+            .iter()
+            .fold(R::ONE, |acc, &raw| acc * (R::ONE + raw));
+        (input + modifiers.flat_sum()) * (R::ONE + modifiers.additive_sum()) * more
+    }
+}
+
+/// All percentages stack additively:
+/// `(input + Σflats) * (1 + Σadditives + Σ(multiplicatives - 1))`.
+///
+/// Multiplicatives keep their usual meaning on their own (`1.5` is 50% more), but two `1.5`
+/// multiplicatives give `2` instead of `2.25`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AdditivePercent;
+
+impl<R: StatNumber + Sub<Output = R>> Formula<R> for AdditivePercent {
+    fn evaluate(input: R, modifiers: &StageModifiers<'_, R>) -> R {
+        let percent = modifiers
+            .multiplicatives
+            .iter()
+            .fold(R::ONE + modifiers.additive_sum(), |acc, &raw| {
+                acc + raw - R::ONE
+            });
+        (input + modifiers.flat_sum()) * percent
+    }
+}
+
+/// Flats are added after the percentages:
+/// `input * (1 + Σadditives) * Πmultiplicatives + Σflats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlatsLast;
+
+impl<R: StatNumber> Formula<R> for FlatsLast {
+    fn evaluate(input: R, modifiers: &StageModifiers<'_, R>) -> R {
+        input * (R::ONE + modifiers.additive_sum()) * modifiers.product() + modifiers.flat_sum()
+    }
+}
@@ -1,32 +1,66 @@
+pub mod bound;
+pub mod curve;
+pub mod derived;
+pub mod error;
+pub mod formula;
 pub mod modifier;
+pub mod number;
+pub mod order;
+pub mod resource;
+pub mod sheet;
+pub mod stacking;
 pub mod stat;
+pub mod timed;
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
 #[cfg(feature = "refcell")]
 pub mod refcell;
 #[cfg(feature = "sync")]
 pub mod sync;
 
 pub mod prelude {
+    pub use crate::formula::{Formula, Standard};
+    pub use crate::modifier::shared::All;
     pub use crate::modifier::Additive;
+    pub use crate::modifier::Ceiling;
     pub use crate::modifier::Flat;
+    pub use crate::modifier::Floor;
     pub use crate::modifier::Modifier;
+    pub use crate::modifier::ModifierId;
     pub use crate::modifier::Multiplicative;
+    pub use crate::modifier::Override;
+    pub use crate::number::StatNumber;
+    pub use crate::order::{At, First, Last, Middle, Order};
+    pub use crate::resource::Resource;
+    pub use crate::stacking::Stacking;
+    pub use crate::stat::observer::ObserverId;
     pub use crate::stat::Stat;
     pub use crate::stat::StatMarker;
-    pub use crate::modifier::shared::All;
 
+    #[cfg(feature = "async")]
+    pub use crate::asynchronous::MiniStat as MiniStatAsync;
+    #[cfg(feature = "refcell")]
+    pub use crate::refcell::MiniResource;
     #[cfg(feature = "refcell")]
     pub use crate::refcell::MiniStat;
     #[cfg(feature = "sync")]
+    pub use crate::sync::MiniResource as MiniResourceSync;
+    #[cfg(feature = "sync")]
     pub use crate::sync::MiniStat as MiniStatSync;
 
-
     /// Flat modifier applicable to all stats.
     pub type FlatAll<R, M> = Flat<All<R, M>, R, M>;
     /// Additive modifier applicable to all stats.
     pub type AdditiveAll<R, M> = Additive<All<R, M>, R, M>;
     /// Multiplicative modifier applicable to all stats.
     pub type MultiplicativeAll<R, M> = Multiplicative<All<R, M>, R, M>;
+    /// Floor modifier applicable to all stats.
+    pub type FloorAll<R, M> = Floor<All<R, M>, R, M>;
+    /// Ceiling modifier applicable to all stats.
+    pub type CeilingAll<R, M> = Ceiling<All<R, M>, R, M>;
+    /// Override modifier applicable to all stats.
+    pub type OverrideAll<R, M> = Override<All<R, M>, R, M>;
 }
 
 mod sealed {
@@ -1,9 +1,15 @@
 use std::{
     marker::PhantomData,
     ops::{Add, Mul},
+    sync::atomic::{self, AtomicU64},
 };
 
-use crate::{sealed::Sealed, stat::StatMarker};
+use crate::{
+    number::StatNumber,
+    order::{Middle, Order},
+    sealed::Sealed,
+    stat::StatMarker,
+};
 
 pub mod shared;
 use shared::{All, Shared};
@@ -15,7 +21,7 @@ pub trait Modifier: Sealed {
     /// See [shared] for ways to apply modifier to groups of stat markers.
     type Target: StatMarker;
 
-    /// Raw modifier type. Currently either `f32` or `f64` are allowed.
+    /// Raw modifier type, see [`StatNumber`].
     type Raw: Copy + PartialEq;
 
     /// Additional metadata, you may want to store with your modifiers (e.g. description).
@@ -38,6 +44,24 @@ pub trait Modifier: Sealed {
     /// ```
     fn from_raw(raw: Self::Raw) -> Self;
 
+    /// Create a modifier value from value of [Raw][Modifier::Raw] type with attached
+    /// [metadata][Modifier::Metadata].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::{Modifier, FlatAll};
+    ///
+    /// let modifier = FlatAll::<f32, &str>::with_metadata(1., "Ring of Strength");
+    ///
+    /// assert_eq!(modifier.metadata(), Some("Ring of Strength"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self;
+
     /// Get value of underlying [Raw][Modifier::Raw] type.
     ///
     /// # Examples
@@ -54,6 +78,114 @@ pub trait Modifier: Sealed {
     /// # }
     /// ```
     fn raw(&self) -> Self::Raw;
+
+    /// Get [metadata][Modifier::Metadata] attached to the modifier, if any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::{Modifier, FlatAll};
+    ///
+    /// let modifier = FlatAll::<f32, &str>::from_raw(1.);
+    ///
+    /// assert_eq!(modifier.metadata(), None);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn metadata(&self) -> Option<Self::Metadata>;
+
+    /// Move the modifier to the evaluation stage `O`.
+    ///
+    /// Modifiers are created at the [`Middle`] stage. Stages are evaluated in ascending order,
+    /// each one using the result of the previous stage as its base value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::{Modifier, FlatAll, First, Middle, Order};
+    ///
+    /// let modifier = FlatAll::<f32, ()>::from_raw(1.).at::<First>();
+    ///
+    /// assert_eq!(modifier.order(), First::INDEX);
+    /// assert_ne!(modifier.order(), Middle::INDEX);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn at<O: Order>(self) -> Self;
+
+    /// Get index of the evaluation stage of the modifier.
+    ///
+    /// See [`Modifier::at`].
+    fn order(&self) -> isize;
+
+    /// Get handle of the modifier, assigned when it gets applied to a [`Stat`][crate::stat::Stat].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Health;
+    ///
+    /// impl StatMarker for Health {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let modifier = Flat::from_raw(1.);
+    /// assert_eq!(modifier.id(), None);
+    ///
+    /// let mut stat = Stat::<Health>::with_base(1.);
+    /// let id = stat.apply_flat(modifier);
+    ///
+    /// assert_eq!(stat.flats()[0].id(), Some(id));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn id(&self) -> Option<ModifierId>;
+}
+
+/// Opaque handle of a modifier applied to a [`Stat`][crate::stat::Stat].
+///
+/// Handles are unique, so identical modifiers from different sources can be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModifierId(u64);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+impl ModifierId {
+    pub(crate) fn next() -> Self {
+        Self(NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ModifierId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Deserialized handles are reserved, so they never get handed out to new modifiers.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ModifierId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let id = u64::deserialize(deserializer)?;
+        let next = id
+            .checked_add(1)
+            .ok_or_else(|| D::Error::custom("modifier handle out of range"))?;
+        NEXT_ID.fetch_max(next, atomic::Ordering::Relaxed);
+        Ok(Self(id))
+    }
 }
 
 /// Flat modifier (e.g. "+1", "-10"). Applied first to the base value.
@@ -72,6 +204,7 @@ pub trait Modifier: Sealed {
 /// # }
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Flat<S, R, M>
 where
     S: StatMarker,
@@ -80,6 +213,9 @@ where
 {
     raw: R,
     metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _target: PhantomData<S>,
 }
 
@@ -90,7 +226,7 @@ where
     M: Copy + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.raw == other.raw && self.metadata == other.metadata
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
     }
 }
 
@@ -118,19 +254,47 @@ where
         Self {
             raw,
             metadata: None,
+            order: Middle::INDEX,
+            id: None,
             _target: PhantomData,
         }
     }
 
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
     fn raw(&self) -> R {
         self.raw
     }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
 }
 
 impl<To, R, M> Shared<To> for Flat<All<R, M>, R, M>
 where
     To: StatMarker,
-    R: Copy + Add<Output = R> + Mul<Output = R> + PartialEq,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     type TargetModifier = Flat<To, R, M>;
@@ -139,6 +303,8 @@ where
         Flat::<To, R, M> {
             raw: self.raw,
             metadata: self.metadata,
+            order: self.order,
+            id: None,
             _target: PhantomData,
         }
     }
@@ -174,23 +340,14 @@ where
     }
 }
 
-impl<S, M> Default for Flat<S, f32, M>
+impl<S, R, M> Default for Flat<S, R, M>
 where
     S: StatMarker,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     fn default() -> Self {
-        Self::from_raw(0.)
-    }
-}
-
-impl<S, M> Default for Flat<S, f64, M>
-where
-    S: StatMarker,
-    M: Copy + PartialEq,
-{
-    fn default() -> Self {
-        Self::from_raw(0.)
+        Self::from_raw(R::ZERO)
     }
 }
 
@@ -210,6 +367,7 @@ where
 /// # }
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Additive<S, R, M>
 where
     S: StatMarker,
@@ -218,6 +376,9 @@ where
 {
     raw: R,
     metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _target: PhantomData<S>,
 }
 
@@ -228,7 +389,7 @@ where
     M: Copy + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.raw == other.raw && self.metadata == other.metadata
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
     }
 }
 
@@ -256,19 +417,47 @@ where
         Self {
             raw,
             metadata: None,
+            order: Middle::INDEX,
+            id: None,
             _target: PhantomData,
         }
     }
 
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
     fn raw(&self) -> R {
         self.raw
     }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
 }
 
 impl<To, R, M> Shared<To> for Additive<All<R, M>, R, M>
 where
     To: StatMarker,
-    R: Copy + Add<Output = R> + Mul<Output = R> + PartialEq,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     type TargetModifier = Additive<To, R, M>;
@@ -277,6 +466,8 @@ where
         Additive::<To, R, M> {
             raw: self.raw,
             metadata: self.metadata,
+            order: self.order,
+            id: None,
             _target: PhantomData,
         }
     }
@@ -312,23 +503,14 @@ where
     }
 }
 
-impl<S, M> Default for Additive<S, f32, M>
-where
-    S: StatMarker,
-    M: Copy + PartialEq,
-{
-    fn default() -> Self {
-        Self::from_raw(1.)
-    }
-}
-
-impl<S, M> Default for Additive<S, f64, M>
+impl<S, R, M> Default for Additive<S, R, M>
 where
     S: StatMarker,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     fn default() -> Self {
-        Self::from_raw(1.)
+        Self::from_raw(R::ONE)
     }
 }
 
@@ -348,6 +530,7 @@ where
 /// # }
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Multiplicative<S, R, M>
 where
     S: StatMarker,
@@ -356,6 +539,9 @@ where
 {
     raw: R,
     metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _target: PhantomData<S>,
 }
 
@@ -366,7 +552,7 @@ where
     M: Copy + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.raw == other.raw && self.metadata == other.metadata
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
     }
 }
 
@@ -394,19 +580,47 @@ where
         Self {
             raw,
             metadata: None,
+            order: Middle::INDEX,
+            id: None,
             _target: PhantomData,
         }
     }
 
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
     fn raw(&self) -> R {
         self.raw
     }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
 }
 
 impl<To, R, M> Shared<To> for Multiplicative<All<R, M>, R, M>
 where
     To: StatMarker,
-    R: Copy + Add<Output = R> + Mul<Output = R> + PartialEq,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     type TargetModifier = Multiplicative<To, R, M>;
@@ -415,6 +629,8 @@ where
         Multiplicative::<To, R, M> {
             raw: self.raw,
             metadata: self.metadata,
+            order: self.order,
+            id: None,
             _target: PhantomData,
         }
     }
@@ -450,22 +666,479 @@ where
     }
 }
 
-impl<S, M> Default for Multiplicative<S, f32, M>
+impl<S, R, M> Default for Multiplicative<S, R, M>
 where
     S: StatMarker,
+    R: StatNumber,
     M: Copy + PartialEq,
 {
     fn default() -> Self {
-        Self::from_raw(1.)
+        Self::from_raw(R::ONE)
     }
 }
 
-impl<S, M> Default for Multiplicative<S, f64, M>
+/// Override modifier (e.g. "= 0" for petrify). Replaces the result of all stages, [floors][Floor]
+/// and [ceilings][Ceiling].
+///
+/// When several overrides are applied, the one with the highest [order][Modifier::at] wins, ties
+/// are resolved in favor of the most recently applied one.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::prelude::{Modifier, Override, All};
+///
+/// let modifier = Override::<All<f32, ()>, f32, ()>::from_raw(0.);
+///
+/// assert_eq!(modifier.raw(), 0.);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Override<S, R, M>
 where
     S: StatMarker,
+    R: Copy + PartialEq,
     M: Copy + PartialEq,
 {
-    fn default() -> Self {
-        Self::from_raw(1.)
+    raw: R,
+    metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _target: PhantomData<S>,
+}
+
+impl<S, R, M> PartialEq for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
+    }
+}
+
+impl<S, R, M> Sealed for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> Modifier for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    type Target = S;
+
+    type Raw = R;
+
+    type Metadata = M;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            raw,
+            metadata: None,
+            order: Middle::INDEX,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
+    fn raw(&self) -> R {
+        self.raw
+    }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
+}
+
+impl<To, R, M> Shared<To> for Override<All<R, M>, R, M>
+where
+    To: StatMarker,
+    R: StatNumber,
+    M: Copy + PartialEq,
+{
+    type TargetModifier = Override<To, R, M>;
+
+    fn share(self) -> Self::TargetModifier {
+        Override::<To, R, M> {
+            raw: self.raw,
+            metadata: self.metadata,
+            order: self.order,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+}
+
+impl<S, R, M> Clone for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, R, M> Copy for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> From<R> for Override<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn from(value: R) -> Self {
+        Self::from_raw(value)
+    }
+}
+
+/// Floor modifier (e.g. "can't drop below 0.5"). Raises the result of all stages to at least its
+/// value.
+///
+/// When several floors are applied, the highest one wins.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::prelude::{Modifier, Floor, All};
+///
+/// let modifier = Floor::<All<f32, ()>, f32, ()>::from_raw(0.5);
+///
+/// assert_eq!(modifier.raw(), 0.5);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    raw: R,
+    metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _target: PhantomData<S>,
+}
+
+impl<S, R, M> PartialEq for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
+    }
+}
+
+impl<S, R, M> Sealed for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> Modifier for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    type Target = S;
+
+    type Raw = R;
+
+    type Metadata = M;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            raw,
+            metadata: None,
+            order: Middle::INDEX,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
+    fn raw(&self) -> R {
+        self.raw
+    }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
+}
+
+impl<To, R, M> Shared<To> for Floor<All<R, M>, R, M>
+where
+    To: StatMarker,
+    R: StatNumber,
+    M: Copy + PartialEq,
+{
+    type TargetModifier = Floor<To, R, M>;
+
+    fn share(self) -> Self::TargetModifier {
+        Floor::<To, R, M> {
+            raw: self.raw,
+            metadata: self.metadata,
+            order: self.order,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+}
+
+impl<S, R, M> Clone for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, R, M> Copy for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> From<R> for Floor<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn from(value: R) -> Self {
+        Self::from_raw(value)
+    }
+}
+
+/// Ceiling modifier (e.g. "capped at 30"). Lowers the result of all stages to at most its value.
+///
+/// When several ceilings are applied, the lowest one wins. Ceilings take precedence over
+/// [floors][Floor].
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::prelude::{Modifier, Ceiling, All};
+///
+/// let modifier = Ceiling::<All<f32, ()>, f32, ()>::from_raw(30.);
+///
+/// assert_eq!(modifier.raw(), 30.);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    raw: R,
+    metadata: Option<M>,
+    order: isize,
+    pub(crate) id: Option<ModifierId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _target: PhantomData<S>,
+}
+
+impl<S, R, M> PartialEq for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw && self.metadata == other.metadata && self.order == other.order
+    }
+}
+
+impl<S, R, M> Sealed for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> Modifier for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    type Target = S;
+
+    type Raw = R;
+
+    type Metadata = M;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            raw,
+            metadata: None,
+            order: Middle::INDEX,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+
+    fn with_metadata(raw: Self::Raw, metadata: Self::Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..Self::from_raw(raw)
+        }
+    }
+
+    fn raw(&self) -> R {
+        self.raw
+    }
+
+    fn metadata(&self) -> Option<M> {
+        self.metadata
+    }
+
+    fn at<O: Order>(self) -> Self {
+        Self {
+            order: O::INDEX,
+            ..self
+        }
+    }
+
+    fn order(&self) -> isize {
+        self.order
+    }
+
+    fn id(&self) -> Option<ModifierId> {
+        self.id
+    }
+}
+
+impl<To, R, M> Shared<To> for Ceiling<All<R, M>, R, M>
+where
+    To: StatMarker,
+    R: StatNumber,
+    M: Copy + PartialEq,
+{
+    type TargetModifier = Ceiling<To, R, M>;
+
+    fn share(self) -> Self::TargetModifier {
+        Ceiling::<To, R, M> {
+            raw: self.raw,
+            metadata: self.metadata,
+            order: self.order,
+            id: None,
+            _target: PhantomData,
+        }
+    }
+}
+
+impl<S, R, M> Clone for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, R, M> Copy for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+}
+
+impl<S, R, M> From<R> for Ceiling<S, R, M>
+where
+    S: StatMarker,
+    R: Copy + PartialEq,
+    M: Copy + PartialEq,
+{
+    fn from(value: R) -> Self {
+        Self::from_raw(value)
     }
 }
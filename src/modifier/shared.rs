@@ -1,9 +1,6 @@
-use std::{
-    marker::PhantomData,
-    ops::{Add, Mul},
-};
+use std::marker::PhantomData;
 
-use crate::{modifier::Modifier, stat::StatMarker};
+use crate::{formula::Standard, modifier::Modifier, number::StatNumber, stat::StatMarker};
 
 /// A shared modifier group ([`StatMarker`]) for modifiers applicable to all user defined stat markers
 /// with same [raw type][Modifier::Raw] and [metadata][Modifier::Metadata].
@@ -24,17 +21,19 @@ use crate::{modifier::Modifier, stat::StatMarker};
 #[derive(Clone, Copy, PartialEq)]
 pub struct All<Raw, M>(PhantomData<Raw>, M)
 where
-    Raw: Copy + PartialEq + Add<Output = Raw> + Mul<Output = Raw>,
+    Raw: StatNumber,
     M: Copy + PartialEq;
 
 impl<Raw, M> StatMarker for All<Raw, M>
 where
-    Raw: Copy + PartialEq + Add<Output = Raw> + Mul<Output = Raw>,
+    Raw: StatNumber,
     M: Copy + PartialEq,
 {
     type Raw = Raw;
 
     type Metadata = M;
+
+    type Formula = Standard;
 }
 
 /// A trait defining a group of modifiers applicable to multiple [stat markers][StatMarker].
@@ -44,44 +43,64 @@ where
 /// # use std::error::Error;
 /// #
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// #[derive(Debug, Default, Clone, PartialEq)]
 /// use mini_stat::{modifier::shared::Shared, prelude::*};
-/// 
+/// #
+/// # #[derive(Debug, Default, Clone, PartialEq)]
+/// # struct A;
+/// #
+/// # impl StatMarker for A {
+/// #     type Raw = f64;
+/// #     type Metadata = &'static str;
+/// #     type Formula = Standard;
+/// # }
+/// #
+/// # #[derive(Debug, Default, Clone, PartialEq)]
+/// # struct B;
+/// #
+/// # impl StatMarker for B {
+/// #     type Raw = f64;
+/// #     type Metadata = &'static str;
+/// #     type Formula = Standard;
+/// # }
+///
+/// #[derive(Debug, Default, Clone, PartialEq)]
 /// pub struct SomeGroup;
-/// 
+///
 /// impl StatMarker for SomeGroup {
 ///     type Raw = f64;
-/// 
+///
 ///     type Metadata = &'static str;
+///
+///     type Formula = Standard;
 /// }
-/// 
+///
 /// impl Shared<A> for Flat<SomeGroup, f64, &'static str> {
 ///     type TargetModifier = Flat<A, f64, &'static str>;
-/// 
+///
 ///     fn share(self) -> Self::TargetModifier {
 ///         Flat::<A, f64, &'static str>::from_raw(self.raw())
 ///     }
 /// }
-/// 
+///
 /// impl Shared<B> for Flat<SomeGroup, f64, &'static str> {
 ///     type TargetModifier = Flat<B, f64, &'static str>;
-/// 
+///
 ///     fn share(self) -> Self::TargetModifier {
 ///         Flat::<B, f64, &'static str>::from_raw(self.raw())
 ///     }
 /// }
-/// 
+///
 /// let mut a = Stat::<A>::with_base(1.);
 /// let mut b = Stat::<B>::with_base(3.);
-/// 
+///
 /// let modifier = Flat::<SomeGroup, f64, &str>::from_raw(2.);
-/// 
+///
 /// a.apply_flat(Shared::<A>::share(modifier));
-/// 
+///
 /// assert_eq!(a.cache_value().cached(), Some(3.));
-/// 
+///
 /// b.apply_flat(Shared::<B>::share(modifier));
-/// 
+///
 /// assert_eq!(b.cache_value().cached(), Some(5.));
 /// #   Ok(())
 /// # }
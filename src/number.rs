@@ -0,0 +1,349 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Numeric type usable as [raw type][crate::stat::StatMarker::Raw] of stats and modifiers.
+///
+/// Implemented for `f32`, `f64`, `i32`, `i64` and [`Fixed`]. Integer and fixed-point types give
+/// bit-exact results across platforms and compilers (e.g. for deterministic lockstep games).
+///
+/// Raw types must be [`PartialOrd`], because [floors][crate::modifier::Floor],
+/// [ceilings][crate::modifier::Ceiling] and [bounds][crate::bound::Bound] compare values.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ops::{Add, Mul};
+///
+/// use mini_stat::prelude::*;
+///
+/// /// Amount of gold in copper coins.
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// struct Copper(u64);
+///
+/// impl Add for Copper {
+///     type Output = Self;
+///
+///     fn add(self, rhs: Self) -> Self {
+///         Self(self.0 + rhs.0)
+///     }
+/// }
+///
+/// impl Mul for Copper {
+///     type Output = Self;
+///
+///     fn mul(self, rhs: Self) -> Self {
+///         Self(self.0 * rhs.0)
+///     }
+/// }
+///
+/// impl StatNumber for Copper {
+///     const ZERO: Self = Self(0);
+///     const ONE: Self = Self(1);
+/// }
+///
+/// struct Price;
+///
+/// impl StatMarker for Price {
+///     type Raw = Copper;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let mut price = Stat::<Price>::with_base(Copper(100));
+/// price.apply_flat(Flat::from_raw(Copper(20)));
+///
+/// assert_eq!(price.cache_value().cached(), Some(Copper(120)));
+/// #   Ok(())
+/// # }
+/// ```
+pub trait StatNumber:
+    Copy + PartialEq + PartialOrd + Add<Output = Self> + Mul<Output = Self>
+{
+    /// Identity element of addition, default value of [flat][crate::modifier::Flat] modifiers.
+    const ZERO: Self;
+
+    /// Identity element of multiplication, default value of [additive][crate::modifier::Additive]
+    /// and [multiplicative][crate::modifier::Multiplicative] modifiers.
+    const ONE: Self;
+
+    /// Packs the number into 64 bits, so [`sync::MiniStat`][crate::sync::MiniStat] can publish
+    /// its final value through an atomic. `None` (the default) for numbers, which don't fit.
+    fn pack(self) -> Option<u64> {
+        None
+    }
+
+    /// Inverse of [`StatNumber::pack`].
+    fn unpack(bits: u64) -> Option<Self> {
+        let _ = bits;
+        None
+    }
+}
+
+impl StatNumber for f32 {
+    const ZERO: Self = 0.;
+    const ONE: Self = 1.;
+
+    fn pack(self) -> Option<u64> {
+        Some(self.to_bits().into())
+    }
+
+    fn unpack(bits: u64) -> Option<Self> {
+        Some(Self::from_bits(bits as u32))
+    }
+}
+
+impl StatNumber for f64 {
+    const ZERO: Self = 0.;
+    const ONE: Self = 1.;
+
+    fn pack(self) -> Option<u64> {
+        Some(self.to_bits())
+    }
+
+    fn unpack(bits: u64) -> Option<Self> {
+        Some(Self::from_bits(bits))
+    }
+}
+
+impl StatNumber for i32 {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn pack(self) -> Option<u64> {
+        Some((self as u32).into())
+    }
+
+    fn unpack(bits: u64) -> Option<Self> {
+        Some(bits as u32 as Self)
+    }
+}
+
+impl StatNumber for i64 {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn pack(self) -> Option<u64> {
+        Some(self as u64)
+    }
+
+    fn unpack(bits: u64) -> Option<Self> {
+        Some(bits as Self)
+    }
+}
+
+/// Floating-point [`StatNumber`], e.g. for [exponential curves][crate::curve::Curve::exponential].
+pub trait StatFloat: StatNumber + Sub<Output = Self> + Div<Output = Self> {
+    /// `e^self`.
+    fn exp(self) -> Self;
+}
+
+impl StatFloat for f32 {
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+}
+
+impl StatFloat for f64 {
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+}
+
+/// Signed Q16.16 fixed-point number.
+///
+/// All arithmetic is done on integers, so results are bit-exact on every platform.
+/// Multiplication and division round towards negative infinity.
+///
+/// Representable values range from [`Fixed::MIN`] (`-32768`) to [`Fixed::MAX`] (`32768 - 2⁻¹⁶`).
+/// Operators saturate at the ends of the range instead of wrapping or panicking, division by zero
+/// gives [`Fixed::MAX`] or [`Fixed::MIN`] depending on the sign of the dividend (zero for `0 / 0`).
+/// Use the `checked_*` methods to detect overflow.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::number::Fixed;
+///
+/// let half = Fixed::from_ratio(1, 2);
+///
+/// assert_eq!(Fixed::from_int(3) * half, Fixed::from_ratio(3, 2));
+/// assert_eq!((Fixed::from_int(3) * half).to_bits(), 0x0001_8000);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Fixed(i32);
+
+impl Fixed {
+    /// Number of fractional bits.
+    pub const FRAC_BITS: u32 = 16;
+
+    /// Smallest representable value, `-32768`.
+    pub const MIN: Self = Self(i32::MIN);
+
+    /// Largest representable value, `32768 - 2⁻¹⁶`.
+    pub const MAX: Self = Self(i32::MAX);
+
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    /// Creates `value`, saturated to the representable range.
+    pub const fn from_int(value: i32) -> Self {
+        Self(saturate((value as i64) << Self::FRAC_BITS))
+    }
+
+    /// Creates `numerator / denominator`, rounded towards negative infinity and saturated to the
+    /// representable range.
+    pub const fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        Self(div_bits(numerator, denominator))
+    }
+
+    /// Integer part, rounded towards negative infinity.
+    pub const fn to_int(self) -> i32 {
+        self.0 >> Self::FRAC_BITS
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1 << Self::FRAC_BITS) as f64
+    }
+
+    /// `self + rhs`, or `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// `self - rhs`, or `None` on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// `self * rhs`, or `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        i32::try_from(mul_bits(self.0, rhs.0)).ok().map(Self)
+    }
+
+    /// `self / rhs`, or `None` on overflow or division by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        quotient_bits(self.0, rhs.0)
+            .and_then(|bits| i32::try_from(bits).ok())
+            .map(Self)
+    }
+}
+
+/// Clamps bits of a Q16.16 number to the representable range.
+const fn saturate(bits: i64) -> i32 {
+    if bits > i32::MAX as i64 {
+        i32::MAX
+    } else if bits < i32::MIN as i64 {
+        i32::MIN
+    } else {
+        bits as i32
+    }
+}
+
+/// Bits of the Q16.16 product of two Q16.16 numbers, before saturation.
+const fn mul_bits(lhs: i32, rhs: i32) -> i64 {
+    (lhs as i64 * rhs as i64) >> Fixed::FRAC_BITS
+}
+
+/// Divides `numerator` by `denominator`, returning bits of the Q16.16 quotient before saturation,
+/// rounded towards negative infinity. `None` if `denominator` is zero.
+const fn quotient_bits(numerator: i32, denominator: i32) -> Option<i64> {
+    if denominator == 0 {
+        return None;
+    }
+    let numerator = (numerator as i64) << Fixed::FRAC_BITS;
+    let denominator = denominator as i64;
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Saturated bits of `numerator / denominator`, see [`Fixed`] for division by zero.
+const fn div_bits(numerator: i32, denominator: i32) -> i32 {
+    match quotient_bits(numerator, denominator) {
+        Some(bits) => saturate(bits),
+        None => saturate(numerator.signum() as i64 * i64::MAX),
+    }
+}
+
+impl StatNumber for Fixed {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self::from_int(1);
+
+    fn pack(self) -> Option<u64> {
+        self.0.pack()
+    }
+
+    fn unpack(bits: u64) -> Option<Self> {
+        i32::unpack(bits).map(Self)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(saturate(mul_bits(self.0, rhs.0)))
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self(div_bits(self.0, rhs.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixed({})", self.to_f64())
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
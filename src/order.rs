@@ -1,13 +1,26 @@
 use crate::sealed::Sealed;
 
-pub trait Order: Sealed {}
+/// Evaluation stage of a [modifier][crate::modifier::Modifier].
+///
+/// Stages are evaluated in ascending [index][Order::INDEX] order. Within a single stage the
+/// result of the previous stage is used as the base value.
+pub trait Order: Sealed {
+    /// Index of the stage.
+    const INDEX: isize;
+}
 
+/// Stage with a user-defined index.
 pub struct At<const INDEX: isize>;
 
-impl<const INDEX: isize> Order for At<INDEX> {}
+impl<const INDEX: isize> Order for At<INDEX> {
+    const INDEX: isize = INDEX;
+}
 
 impl<const INDEX: isize> Sealed for At<INDEX> {}
 
+/// Stage evaluated before all other stages.
 pub type First = At<{ isize::MIN }>;
+/// Stage evaluated after all other stages.
 pub type Last = At<{ isize::MAX }>;
+/// Default stage of every modifier.
 pub type Middle = At<0>;
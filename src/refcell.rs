@@ -1,34 +1,52 @@
 use std::{
-    cell::{RefCell, RefMut},
+    cell::{Ref, RefCell, RefMut},
     fmt::Debug,
+    ops::{Div, Sub},
 };
 
+use smallvec::SmallVec;
+
 use crate::{
-    modifier::shared::Shared,
-    prelude::{Additive, Flat, Multiplicative, Stat, StatMarker},
+    error::Error,
+    modifier::{shared::Shared, ModifierId},
+    prelude::{Additive, Ceiling, Flat, Floor, Multiplicative, Override, Stat, StatMarker},
+    resource::Resource,
+    stacking::Stacking,
+    stat::{
+        breakdown::Breakdown,
+        observer::{Callback, IntoCallback, ObserverId},
+    },
+    timed::{Duration, Tick},
 };
 
-pub struct MiniStat<Marker, const N: usize = 2>(RefCell<Stat<Marker, N>>)
+/// [`Stat`] behind a [`RefCell`]. Observers are of type `O`, see [`Stat`]; with
+/// [`LocalCallback`][crate::stat::observer::LocalCallback] they may capture an `Rc` (e.g. of the
+/// `MiniStat` itself).
+pub struct MiniStat<Marker, const N: usize = 2, O = Callback<<Marker as StatMarker>::Raw>>(
+    RefCell<Stat<Marker, N, O>>,
+)
 where
-    Marker: StatMarker;
+    Marker: StatMarker,
+    O: ?Sized;
 
-impl<Marker, const N: usize> MiniStat<Marker, N>
+impl<Marker, const N: usize, O> MiniStat<Marker, N, O>
 where
     Marker: StatMarker,
-    Stat<Marker, N>: Default,
-    Flat<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Additive<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Multiplicative<Marker, Marker::Raw, Marker::Metadata>: Default,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
 {
-    pub fn new(stat: Stat<Marker, N>) -> Self {
+    pub fn new(stat: Stat<Marker, N, O>) -> Self {
         Self(RefCell::new(stat))
     }
 
     pub fn with_base(base: Marker::Raw) -> Self {
-        Self(RefCell::new(Stat::<Marker, N>::with_base(base)))
+        Self(RefCell::new(Stat::with_base(base)))
+    }
+
+    pub fn stat(&self) -> Ref<'_, Stat<Marker, N, O>> {
+        self.0.borrow()
     }
 
-    pub fn stat_mut(&self) -> RefMut<Stat<Marker, N>> {
+    pub fn stat_mut(&self) -> RefMut<'_, Stat<Marker, N, O>> {
         self.0.borrow_mut()
     }
 
@@ -36,42 +54,212 @@ where
         self.0.borrow().base
     }
 
+    pub fn set_base(&self, base: Marker::Raw) {
+        self.0.borrow_mut().set_base(base);
+    }
+
+    pub fn add_to_base(&self, amount: Marker::Raw) {
+        self.0.borrow_mut().add_to_base(amount);
+    }
+
+    pub fn map_base(&self, f: impl FnOnce(Marker::Raw) -> Marker::Raw) {
+        self.0.borrow_mut().map_base(f);
+    }
+
     pub fn cached(&self) -> Marker::Raw {
         self.0.borrow_mut().cache_value();
         self.0.borrow().cached().unwrap()
     }
 
-    pub fn apply_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.borrow_mut().apply_flat(flat);
+    pub fn breakdown(&self) -> Breakdown<Marker> {
+        self.0.borrow().breakdown()
     }
 
-    pub fn apply_flat_from_shared<T>(&self, flat: T)
+    pub fn apply_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.0.borrow_mut().apply_flat(flat)
+    }
+
+    pub fn apply_flat_from_shared<T>(&self, flat: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Flat<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.borrow_mut().apply_flat_from_shared(flat);
+        self.0.borrow_mut().apply_flat_from_shared(flat)
     }
 
-    pub fn apply_add(&self, add: Additive<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.borrow_mut().apply_add(add);
+    pub fn apply_add(&self, add: Additive<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.0.borrow_mut().apply_add(add)
     }
 
-    pub fn apply_add_from_shared<T>(&self, add: T)
+    pub fn apply_add_from_shared<T>(&self, add: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Additive<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.borrow_mut().apply_add_from_shared(add);
+        self.0.borrow_mut().apply_add_from_shared(add)
     }
 
-    pub fn apply_mul(&self, mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.borrow_mut().apply_mul(mul);
+    pub fn apply_mul(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_mul(mul)
     }
 
-    pub fn apply_mul_from_shared<T>(&self, mul: T)
+    pub fn apply_mul_from_shared<T>(&self, mul: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.borrow_mut().apply_mul_from_shared(mul);
+        self.0.borrow_mut().apply_mul_from_shared(mul)
+    }
+
+    pub fn apply_floor(&self, floor: Floor<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.0.borrow_mut().apply_floor(floor)
+    }
+
+    pub fn apply_floor_from_shared<T>(&self, floor: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.0.borrow_mut().apply_floor_from_shared(floor)
+    }
+
+    pub fn apply_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_ceiling(ceiling)
+    }
+
+    pub fn apply_ceiling_from_shared<T>(&self, ceiling: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.0.borrow_mut().apply_ceiling_from_shared(ceiling)
+    }
+
+    pub fn apply_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_override(modifier)
+    }
+
+    pub fn apply_override_from_shared<T>(&self, modifier: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Override<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.0.borrow_mut().apply_override_from_shared(modifier)
+    }
+
+    pub fn apply_flat_timed(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_flat_timed(flat, duration)
+    }
+
+    pub fn apply_add_timed(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_add_timed(add, duration)
+    }
+
+    pub fn apply_mul_timed(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_mul_timed(mul, duration)
+    }
+
+    pub fn apply_floor_timed(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_floor_timed(floor, duration)
+    }
+
+    pub fn apply_ceiling_timed(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_ceiling_timed(ceiling, duration)
+    }
+
+    pub fn apply_override_timed(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_override_timed(modifier, duration)
+    }
+
+    pub fn apply_flat_if<C: 'static>(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_flat_if(flat, condition)
+    }
+
+    pub fn apply_add_if<C: 'static>(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_add_if(add, condition)
+    }
+
+    pub fn apply_mul_if<C: 'static>(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_mul_if(mul, condition)
+    }
+
+    pub fn apply_floor_if<C: 'static>(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_floor_if(floor, condition)
+    }
+
+    pub fn apply_ceiling_if<C: 'static>(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_ceiling_if(ceiling, condition)
+    }
+
+    pub fn apply_override_if<C: 'static>(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.0.borrow_mut().apply_override_if(modifier, condition)
+    }
+
+    pub fn evaluate_with<C: 'static>(&self, context: &C) -> Marker::Raw {
+        self.0.borrow_mut().evaluate_with(context)
+    }
+
+    pub fn is_stale<C: 'static>(&self, context: &C) -> bool {
+        self.0.borrow().is_stale(context)
+    }
+
+    pub fn tick(&self, delta: Tick) -> SmallVec<[ModifierId; N]> {
+        self.0.borrow_mut().tick(delta)
+    }
+
+    pub fn remaining(&self, id: ModifierId) -> Option<Duration> {
+        self.0.borrow().remaining(id)
     }
 
     pub fn remove_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) {
@@ -88,9 +276,456 @@ where
     ) {
         self.0.borrow_mut().remove_mul(multiplicative);
     }
+
+    pub fn remove_floor(&self, floor: Floor<Marker, Marker::Raw, Marker::Metadata>) {
+        self.0.borrow_mut().remove_floor(floor);
+    }
+
+    pub fn remove_ceiling(&self, ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>) {
+        self.0.borrow_mut().remove_ceiling(ceiling);
+    }
+
+    pub fn remove_override(&self, modifier: Override<Marker, Marker::Raw, Marker::Metadata>) {
+        self.0.borrow_mut().remove_override(modifier);
+    }
+
+    pub fn remove_by_id(&self, id: ModifierId) {
+        self.0.borrow_mut().remove_by_id(id);
+    }
+
+    pub fn remove_with_metadata(&self, metadata: Marker::Metadata) {
+        self.0.borrow_mut().remove_with_metadata(metadata);
+    }
+
+    pub fn set_stacking(&self, metadata: Marker::Metadata, stacking: Stacking) {
+        self.0.borrow_mut().set_stacking(metadata, stacking);
+    }
+
+    /// See [`Stat::subscribe`]. Observers are called while the stat is borrowed, so they may access
+    /// this `MiniStat` only through its `try_` methods, which return [`Error::BorrowConflict`].
+    pub fn subscribe<F>(&self, observer: F) -> ObserverId
+    where
+        F: FnMut(Marker::Raw, Marker::Raw) + IntoCallback<O>,
+    {
+        self.0.borrow_mut().subscribe(observer)
+    }
+
+    pub fn unsubscribe(&self, id: ObserverId) {
+        self.0.borrow_mut().unsubscribe(id);
+    }
+
+    /// See [`Stat::batch`]. The stat stays borrowed for the whole batch.
+    pub fn batch<T, E>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, N, O>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        self.0.borrow_mut().batch(f)
+    }
+}
+
+/// Fallible variants, returning [`Error::BorrowConflict`] instead of panicking when the stat is
+/// already borrowed.
+impl<Marker, const N: usize, O> MiniStat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+{
+    pub fn try_stat(&self) -> Result<Ref<'_, Stat<Marker, N, O>>, Error> {
+        Ok(self.0.try_borrow()?)
+    }
+
+    pub fn try_stat_mut(&self) -> Result<RefMut<'_, Stat<Marker, N, O>>, Error> {
+        Ok(self.0.try_borrow_mut()?)
+    }
+
+    /// Final value of the stat. While the stat is borrowed immutably, the cached value is
+    /// returned, or [`Error::CacheMissing`] if it's stale.
+    pub fn try_cached(&self) -> Result<Marker::Raw, Error> {
+        if let Ok(mut stat) = self.0.try_borrow_mut() {
+            return stat.cache_value().cached().ok_or(Error::CacheMissing);
+        }
+        self.0.try_borrow()?.cached().ok_or(Error::CacheMissing)
+    }
+
+    pub fn try_set_base(&self, base: Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.set_base(base);
+        Ok(())
+    }
+
+    pub fn try_add_to_base(&self, amount: Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.add_to_base(amount);
+        Ok(())
+    }
+
+    pub fn try_map_base(&self, f: impl FnOnce(Marker::Raw) -> Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.map_base(f);
+        Ok(())
+    }
+
+    pub fn try_apply_flat(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat(flat))
+    }
+
+    pub fn try_apply_flat_from_shared<T>(&self, flat: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Flat<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_flat_from_shared(flat))
+    }
+
+    pub fn try_apply_add(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add(add))
+    }
+
+    pub fn try_apply_add_from_shared<T>(&self, add: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Additive<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_add_from_shared(add))
+    }
+
+    pub fn try_apply_mul(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul(mul))
+    }
+
+    pub fn try_apply_mul_from_shared<T>(&self, mul: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_mul_from_shared(mul))
+    }
+
+    pub fn try_apply_floor(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor(floor))
+    }
+
+    pub fn try_apply_floor_from_shared<T>(&self, floor: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_floor_from_shared(floor))
+    }
+
+    pub fn try_apply_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling(ceiling))
+    }
+
+    pub fn try_apply_ceiling_from_shared<T>(&self, ceiling: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_ceiling_from_shared(ceiling))
+    }
+
+    pub fn try_apply_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_override(modifier))
+    }
+
+    pub fn try_apply_override_from_shared<T>(&self, modifier: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Override<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_override_from_shared(modifier))
+    }
+
+    pub fn try_apply_flat_timed(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat_timed(flat, duration))
+    }
+
+    pub fn try_apply_add_timed(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add_timed(add, duration))
+    }
+
+    pub fn try_apply_mul_timed(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul_timed(mul, duration))
+    }
+
+    pub fn try_apply_floor_timed(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor_timed(floor, duration))
+    }
+
+    pub fn try_apply_ceiling_timed(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling_timed(ceiling, duration))
+    }
+
+    pub fn try_apply_override_timed(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self
+            .try_stat_mut()?
+            .apply_override_timed(modifier, duration))
+    }
+
+    pub fn try_apply_flat_if<C: 'static>(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat_if(flat, condition))
+    }
+
+    pub fn try_apply_add_if<C: 'static>(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add_if(add, condition))
+    }
+
+    pub fn try_apply_mul_if<C: 'static>(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul_if(mul, condition))
+    }
+
+    pub fn try_apply_floor_if<C: 'static>(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor_if(floor, condition))
+    }
+
+    pub fn try_apply_ceiling_if<C: 'static>(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling_if(ceiling, condition))
+    }
+
+    pub fn try_apply_override_if<C: 'static>(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_override_if(modifier, condition))
+    }
+
+    pub fn try_evaluate_with<C: 'static>(&self, context: &C) -> Result<Marker::Raw, Error> {
+        Ok(self.try_stat_mut()?.evaluate_with(context))
+    }
+
+    pub fn try_tick(&self, delta: Tick) -> Result<SmallVec<[ModifierId; N]>, Error> {
+        Ok(self.try_stat_mut()?.tick(delta))
+    }
+
+    pub fn try_remove_flat(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_flat(flat);
+        Ok(())
+    }
+
+    pub fn try_remove_add(
+        &self,
+        additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_add(additive);
+        Ok(())
+    }
+
+    pub fn try_remove_mul(
+        &self,
+        multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_mul(multiplicative);
+        Ok(())
+    }
+
+    pub fn try_remove_floor(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_floor(floor);
+        Ok(())
+    }
+
+    pub fn try_remove_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_ceiling(ceiling);
+        Ok(())
+    }
+
+    pub fn try_remove_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_override(modifier);
+        Ok(())
+    }
+
+    pub fn try_remove_by_id(&self, id: ModifierId) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_by_id(id);
+        Ok(())
+    }
+
+    pub fn try_remove_with_metadata(&self, metadata: Marker::Metadata) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_with_metadata(metadata);
+        Ok(())
+    }
+
+    pub fn try_set_stacking(
+        &self,
+        metadata: Marker::Metadata,
+        stacking: Stacking,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.set_stacking(metadata, stacking);
+        Ok(())
+    }
+
+    pub fn try_subscribe<F>(&self, observer: F) -> Result<ObserverId, Error>
+    where
+        F: FnMut(Marker::Raw, Marker::Raw) + IntoCallback<O>,
+    {
+        Ok(self.try_stat_mut()?.subscribe(observer))
+    }
+
+    pub fn try_unsubscribe(&self, id: ObserverId) -> Result<(), Error> {
+        self.try_stat_mut()?.unsubscribe(id);
+        Ok(())
+    }
+
+    /// See [`MiniStat::batch`]. The outer error is about accessing the stat, the inner one is
+    /// returned by `f`.
+    pub fn try_batch<T, E>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, N, O>) -> Result<T, E>,
+    ) -> Result<Result<T, E>, Error> {
+        Ok(self.try_stat_mut()?.batch(f))
+    }
+}
+
+impl<Marker, const N: usize, O> Debug for MiniStat<Marker, N, O>
+where
+    Marker: StatMarker + Debug,
+    O: ?Sized,
+    <Marker as StatMarker>::Raw: Debug,
+    <Marker as StatMarker>::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.borrow().fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Marker, const N: usize, O> serde::Serialize for MiniStat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized,
+    Marker::Raw: serde::Serialize,
+    Marker::Metadata: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.borrow().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Marker, const N: usize, O> serde::Deserialize<'de> for MiniStat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+    Marker::Raw: serde::Deserialize<'de>,
+    Marker::Metadata: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Stat::deserialize(deserializer).map(|stat| Self(RefCell::new(stat)))
+    }
+}
+
+pub struct MiniResource<Marker, const N: usize = 2>(RefCell<Resource<Marker, N>>)
+where
+    Marker: StatMarker;
+
+impl<Marker, const N: usize> MiniResource<Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    pub fn new(resource: Resource<Marker, N>) -> Self {
+        Self(RefCell::new(resource))
+    }
+
+    pub fn with_max(base: Marker::Raw) -> Self {
+        Self(RefCell::new(Resource::<Marker, N>::with_max(base)))
+    }
+
+    pub fn resource_mut(&self) -> RefMut<'_, Resource<Marker, N>> {
+        self.0.borrow_mut()
+    }
+
+    pub fn current(&self) -> Marker::Raw {
+        self.0.borrow().current()
+    }
+
+    pub fn max(&self) -> Marker::Raw {
+        self.0.borrow().max()
+    }
+
+    pub fn damage(&self, amount: Marker::Raw) -> Marker::Raw {
+        self.0.borrow_mut().damage(amount)
+    }
+
+    pub fn heal(&self, amount: Marker::Raw) -> Marker::Raw {
+        self.0.borrow_mut().heal(amount)
+    }
+
+    pub fn spend(&self, amount: Marker::Raw) -> bool {
+        self.0.borrow_mut().spend(amount)
+    }
+
+    pub fn regen(&self, dt: Marker::Raw) -> Marker::Raw {
+        self.0.borrow_mut().regen(dt)
+    }
 }
 
-impl<Marker, const N: usize> Debug for MiniStat<Marker, N>
+impl<Marker, const N: usize> Debug for MiniResource<Marker, N>
 where
     Marker: StatMarker + Debug,
     <Marker as StatMarker>::Raw: Debug,
@@ -0,0 +1,285 @@
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut, Div, Sub},
+};
+
+use crate::{
+    number::StatNumber,
+    prelude::{Stat, StatMarker},
+};
+
+/// How the current value of a [`Resource`] follows changes of its maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MaxPolicy {
+    /// Current value stays the same, even if it exceeds the new maximum.
+    KeepAbsolute,
+    /// Current value keeps the same percentage of the maximum.
+    KeepPercent,
+    /// Current value stays the same, but never exceeds the maximum.
+    #[default]
+    Clamp,
+}
+
+/// Resource (e.g. health, mana, stamina) with a current value bounded by a [`Stat`] maximum.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::{prelude::*, resource::MaxPolicy};
+///
+/// struct Health;
+///
+/// impl StatMarker for Health {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let mut health = Resource::<Health>::with_max(100.);
+/// health.set_policy(MaxPolicy::KeepPercent);
+///
+/// health.damage(50.);
+/// assert_eq!(health.current(), 50.);
+///
+/// health.max_mut().apply_mul(Multiplicative::from_raw(2.));
+/// assert_eq!(health.current(), 100.);
+/// assert_eq!(health.max(), 200.);
+/// #   Ok(())
+/// # }
+/// ```
+pub struct Resource<Marker, const N: usize = 2>
+where
+    Marker: StatMarker,
+{
+    max: Stat<Marker, N>,
+    last_max: Marker::Raw,
+    current: Marker::Raw,
+    regen: Marker::Raw,
+    policy: MaxPolicy,
+}
+
+impl<Marker, const N: usize> Resource<Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    /// Creates a full resource with the given maximum.
+    pub fn new(mut max: Stat<Marker, N>) -> Self {
+        let value = Self::value_of(&mut max);
+        Self {
+            max,
+            last_max: value,
+            current: value,
+            regen: Marker::Raw::ZERO,
+            policy: MaxPolicy::default(),
+        }
+    }
+
+    /// Creates a full resource with the given base maximum.
+    pub fn with_max(base: Marker::Raw) -> Self {
+        Self::new(Stat::with_base(base))
+    }
+
+    pub fn current(&self) -> Marker::Raw {
+        self.current
+    }
+
+    /// Maximum as of the last change of the resource. Changes of
+    /// [dynamic bounds][crate::bound::Bound::Dynamic] of the maximum are picked up by
+    /// [`Resource::refresh`] and by any operation changing the current value.
+    pub fn max(&self) -> Marker::Raw {
+        self.last_max
+    }
+
+    /// Current value as a fraction of the maximum, zero if the maximum is zero.
+    pub fn percent(&self) -> Marker::Raw {
+        if self.last_max == Marker::Raw::ZERO {
+            return Marker::Raw::ZERO;
+        }
+        self.current / self.last_max
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.current <= Marker::Raw::ZERO
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.current >= self.last_max
+    }
+
+    /// Stat of the maximum. The current value gets adjusted according to the
+    /// [policy][MaxPolicy], once the returned guard is dropped.
+    pub fn max_mut(&mut self) -> MaxMut<'_, Marker, N> {
+        MaxMut(self)
+    }
+
+    pub fn policy(&self) -> MaxPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: MaxPolicy) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Amount regenerated per unit of time, see [`Resource::regen`].
+    pub fn regen_rate(&self) -> Marker::Raw {
+        self.regen
+    }
+
+    pub fn set_regen_rate(&mut self, rate: Marker::Raw) -> &mut Self {
+        self.regen = rate;
+        self
+    }
+
+    /// Re-reads the maximum and adjusts the current value according to the [policy][MaxPolicy].
+    pub fn refresh(&mut self) -> &mut Self {
+        self.sync();
+        self
+    }
+
+    /// Decreases the current value by `amount`, down to zero. Negative amounts take nothing.
+    ///
+    /// Returns the amount actually taken.
+    pub fn damage(&mut self, amount: Marker::Raw) -> Marker::Raw {
+        self.sync();
+        if amount <= Marker::Raw::ZERO {
+            return Marker::Raw::ZERO;
+        }
+        let taken = if amount > self.current {
+            self.current
+        } else {
+            amount
+        };
+        self.current = self.current - taken;
+        taken
+    }
+
+    /// Increases the current value by `amount`, up to the maximum. Negative amounts restore
+    /// nothing.
+    ///
+    /// Returns the amount actually restored.
+    pub fn heal(&mut self, amount: Marker::Raw) -> Marker::Raw {
+        self.sync();
+        if amount <= Marker::Raw::ZERO || self.current >= self.last_max {
+            return Marker::Raw::ZERO;
+        }
+        let missing = self.last_max - self.current;
+        let healed = if amount > missing { missing } else { amount };
+        self.current = self.current + healed;
+        healed
+    }
+
+    /// Decreases the current value by `amount`, if there is enough of it.
+    ///
+    /// Returns `false` and leaves the resource untouched otherwise, or if `amount` is negative.
+    pub fn spend(&mut self, amount: Marker::Raw) -> bool {
+        self.sync();
+        if amount < Marker::Raw::ZERO || amount > self.current {
+            return false;
+        }
+        self.current = self.current - amount;
+        true
+    }
+
+    /// Regenerates the resource by [regen rate][Resource::regen_rate] multiplied by `dt`.
+    pub fn regen(&mut self, dt: Marker::Raw) -> Marker::Raw {
+        self.heal(self.regen * dt)
+    }
+
+    /// Restores the resource to its maximum.
+    pub fn fill(&mut self) -> &mut Self {
+        self.sync();
+        self.current = self.last_max;
+        self
+    }
+
+    fn sync(&mut self) {
+        let max = Self::value_of(&mut self.max);
+        if max == self.last_max {
+            return;
+        }
+        self.current = match self.policy {
+            MaxPolicy::KeepAbsolute => self.current,
+            MaxPolicy::KeepPercent if self.last_max == Marker::Raw::ZERO => max,
+            MaxPolicy::KeepPercent => self.current * max / self.last_max,
+            MaxPolicy::Clamp if self.current > max => max,
+            MaxPolicy::Clamp => self.current,
+        };
+        self.last_max = max;
+    }
+
+    fn value_of(max: &mut Stat<Marker, N>) -> Marker::Raw {
+        max.cache_value()
+            .cached()
+            .expect("stat is cached after cache_value")
+    }
+}
+
+impl<Marker, const N: usize> Debug for Resource<Marker, N>
+where
+    Marker: StatMarker + Debug,
+    Marker::Raw: Debug,
+    Marker::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resource")
+            .field("current", &self.current)
+            .field("max", &self.max)
+            .field("regen", &self.regen)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<Marker: StatMarker, const N: usize> Clone for Resource<Marker, N> {
+    fn clone(&self) -> Self {
+        Self {
+            max: self.max.clone(),
+            last_max: self.last_max,
+            current: self.current,
+            regen: self.regen,
+            policy: self.policy,
+        }
+    }
+}
+
+/// Mutable access to the maximum of a [`Resource`], returned from [`Resource::max_mut`].
+pub struct MaxMut<'a, Marker, const N: usize>(&'a mut Resource<Marker, N>)
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>;
+
+impl<Marker, const N: usize> Deref for MaxMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    type Target = Stat<Marker, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.max
+    }
+}
+
+impl<Marker, const N: usize> DerefMut for MaxMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0.max
+    }
+}
+
+impl<Marker, const N: usize> Drop for MaxMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    fn drop(&mut self) {
+        self.0.sync();
+    }
+}
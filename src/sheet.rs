@@ -0,0 +1,176 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug},
+};
+
+use crate::{
+    modifier::ModifierId,
+    number::StatNumber,
+    prelude::{All, Modifier, Stat, StatMarker},
+    stat::erased::ErasedStat,
+};
+
+/// Container holding stats with different [markers][StatMarker] of a single entity.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mini_stat::{prelude::*, sheet::StatSheet};
+///
+/// struct Strength;
+///
+/// impl StatMarker for Strength {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// struct Agility;
+///
+/// impl StatMarker for Agility {
+///     type Raw = f32;
+///     type Metadata = ();
+///     type Formula = Standard;
+/// }
+///
+/// let mut sheet = StatSheet::<2>::new();
+/// sheet
+///     .insert(Stat::<Strength>::with_base(1.))
+///     .insert(Stat::<Agility>::with_base(2.));
+///
+/// sheet.apply_to_all(FlatAll::<f32, ()>::from_raw(1.));
+///
+/// assert_eq!(sheet.value::<Strength>(), Some(2.));
+/// assert_eq!(sheet.value::<Agility>(), Some(3.));
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct StatSheet<const N: usize = 2> {
+    stats: HashMap<TypeId, (&'static str, Box<dyn ErasedStat>)>,
+}
+
+impl<const N: usize> StatSheet<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a stat into the sheet, replacing the previous stat with the same marker.
+    pub fn insert<Marker>(&mut self, stat: Stat<Marker, N>) -> &mut Self
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.stats.insert(
+            TypeId::of::<Marker>(),
+            (type_name::<Marker>(), Box::new(stat)),
+        );
+        self
+    }
+
+    /// Removes the stat marked with `Marker` from the sheet.
+    pub fn remove<Marker>(&mut self) -> Option<Stat<Marker, N>>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.stats
+            .remove(&TypeId::of::<Marker>())
+            .and_then(|(_, stat)| stat.into_any().downcast().ok())
+            .map(|stat| *stat)
+    }
+
+    pub fn contains<Marker>(&self) -> bool
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.stats.contains_key(&TypeId::of::<Marker>())
+    }
+
+    pub fn get<Marker>(&self) -> Option<&Stat<Marker, N>>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.stats
+            .get(&TypeId::of::<Marker>())
+            .and_then(|(_, stat)| stat.as_any().downcast_ref())
+    }
+
+    pub fn get_mut<Marker>(&mut self) -> Option<&mut Stat<Marker, N>>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.stats
+            .get_mut(&TypeId::of::<Marker>())
+            .and_then(|(_, stat)| stat.as_any_mut().downcast_mut())
+    }
+
+    /// Final value of the stat marked with `Marker`.
+    pub fn value<Marker>(&mut self) -> Option<Marker::Raw>
+    where
+        Marker: StatMarker + 'static,
+    {
+        self.get_mut::<Marker>()
+            .and_then(|stat| stat.cache_value().cached())
+    }
+
+    /// Applies a modifier [shared][crate::modifier::shared::All] by all stats (e.g.
+    /// [`FlatAll`][crate::prelude::FlatAll]) to every stat with the same
+    /// [raw type][StatMarker::Raw] and [metadata][StatMarker::Metadata].
+    ///
+    /// Returns handles of the applied modifiers. Modifiers of a single stat are rejected at
+    /// compile time:
+    ///
+    /// ```rust,compile_fail
+    /// use mini_stat::{prelude::*, sheet::StatSheet};
+    ///
+    /// struct Strength;
+    ///
+    /// impl StatMarker for Strength {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut sheet = StatSheet::<2>::new();
+    /// sheet.apply_to_all(Flat::<Strength, f32, ()>::from_raw(1.));
+    /// ```
+    pub fn apply_to_all<T, R, M>(&mut self, modifier: T) -> Vec<ModifierId>
+    where
+        T: Modifier<Target = All<R, M>, Raw = R, Metadata = M> + Any,
+        R: StatNumber,
+        M: Copy + PartialEq,
+    {
+        self.stats
+            .values_mut()
+            .filter_map(|(_, stat)| stat.apply_shared(&modifier))
+            .collect()
+    }
+
+    /// Removes the modifier with handle `id` from whichever stat it was applied to.
+    pub fn remove_by_id(&mut self, id: ModifierId) -> &mut Self {
+        for (_, stat) in self.stats.values_mut() {
+            if stat.remove_by_id(id) {
+                break;
+            }
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+}
+
+impl<const N: usize> Debug for StatSheet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.stats.values().map(|(name, _)| name))
+            .finish()
+    }
+}
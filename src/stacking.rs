@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+use crate::modifier::ModifierId;
+
+/// How modifiers of the same kind with the same [metadata][crate::stat::StatMarker::Metadata]
+/// stack with each other.
+///
+/// See [`Stat::set_stacking`][crate::stat::Stat::set_stacking].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stacking {
+    /// All modifiers apply.
+    #[default]
+    Stack,
+    /// Only the modifier with the highest value applies.
+    HighestOnly,
+    /// Only the modifier with the lowest value applies (e.g. the strongest slow).
+    LowestOnly,
+    /// Only the first applied modifier applies.
+    Unique,
+    /// Only the first `n` applied modifiers apply.
+    MaxStacks(usize),
+}
+
+impl Stacking {
+    /// Returns handles of the modifiers suppressed by the policy.
+    pub(crate) fn suppressed<R: PartialOrd>(
+        self,
+        mut modifiers: Vec<(ModifierId, R)>,
+    ) -> impl Iterator<Item = ModifierId> {
+        // Handles are handed out in ascending order, so sorting by them restores the order in
+        // which the modifiers were applied.
+        modifiers.sort_by_key(|(id, _)| *id);
+        let kept = match self {
+            Self::Stack => 0..modifiers.len(),
+            Self::HighestOnly => Self::best(&modifiers, |raw, best| raw > best),
+            Self::LowestOnly => Self::best(&modifiers, |raw, best| raw < best),
+            Self::Unique => 0..1,
+            Self::MaxStacks(n) => 0..n,
+        };
+        modifiers
+            .into_iter()
+            .enumerate()
+            .filter(move |(i, _)| !kept.contains(i))
+            .map(|(_, (id, _))| id)
+    }
+
+    /// Range containing only the first modifier, which is `better` than all the others.
+    fn best<R>(modifiers: &[(ModifierId, R)], better: impl Fn(&R, &R) -> bool) -> Range<usize> {
+        let mut best = 0;
+        for (i, (_, raw)) in modifiers.iter().enumerate().skip(1) {
+            if better(raw, &modifiers[best].1) {
+                best = i;
+            }
+        }
+        best..best + 1
+    }
+}
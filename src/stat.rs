@@ -1,53 +1,124 @@
-use std::ops::{Add, Mul};
+use std::{any::Any, collections::BTreeMap, fmt::Debug, mem, sync::Arc, thread};
 
 use smallvec::SmallVec;
 
-use crate::modifier::{shared::Shared, *};
+use crate::{
+    bound::Bound,
+    curve::Curve,
+    formula::{Formula, StageModifiers},
+    modifier::{shared::Shared, *},
+    number::StatNumber,
+    stacking::Stacking,
+    timed::{Duration, Tick},
+};
+
+pub mod breakdown;
+pub(crate) mod erased;
+pub mod observer;
+#[cfg(feature = "serde")]
+mod serialize;
+use breakdown::{Breakdown, StageBreakdown};
+use observer::{Callback, IntoCallback, ObserverId, Observers};
 
 pub trait StatMarker {
-    type Raw: Copy + PartialEq + Add<Output = Self::Raw> + Mul<Output = Self::Raw>;
+    type Raw: StatNumber;
     type Metadata: Copy + PartialEq;
+    /// How each stage of the stat is evaluated, usually [`Standard`][crate::formula::Standard].
+    type Formula: Formula<Self::Raw>;
 }
 
-#[derive(Debug)]
-pub struct Stat<Marker, const N: usize = 2>
+/// [Flat] modifiers applied to a [`Stat`].
+pub type Flats<Marker, const N: usize> =
+    SmallVec<[Flat<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>;
+/// [Additive] modifiers applied to a [`Stat`].
+pub type Additives<Marker, const N: usize> =
+    SmallVec<[Additive<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>;
+/// [Multiplicative] modifiers applied to a [`Stat`].
+pub type Multiplicatives<Marker, const N: usize> = SmallVec<
+    [Multiplicative<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N],
+>;
+/// [Floor] modifiers applied to a [`Stat`].
+pub type Floors<Marker, const N: usize> =
+    SmallVec<[Floor<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>;
+/// [Ceiling] modifiers applied to a [`Stat`].
+pub type Ceilings<Marker, const N: usize> =
+    SmallVec<[Ceiling<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>;
+/// [Override] modifiers applied to a [`Stat`].
+pub type Overrides<Marker, const N: usize> =
+    SmallVec<[Override<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>;
+
+/// Predicate of a conditional modifier over a type erased context.
+pub(crate) type Condition = Arc<dyn Fn(&dyn Any) -> bool + Send + Sync>;
+
+/// Takes away the parts of the final value [converted][crate::derived::StatGraph::convert] to
+/// other stats.
+pub(crate) type Conversion<R> = Arc<dyn Fn(R) -> R + Send + Sync>;
+
+/// Stat with modifiers applied to its base value.
+///
+/// Observers are of type `O`, [`Callback`] (the default) or
+/// [`LocalCallback`][observer::LocalCallback] for observers, which can't be sent between threads.
+pub struct Stat<Marker, const N: usize = 2, O = Callback<<Marker as StatMarker>::Raw>>
 where
     Marker: StatMarker,
+    O: ?Sized,
 {
     pub(crate) base: Marker::Raw,
     pub(crate) cached: Option<Marker::Raw>,
-    pub(crate) flats: SmallVec<[Flat<Marker, Marker::Raw, Marker::Metadata>; N]>,
-    pub(crate) adds: SmallVec<[Additive<Marker, Marker::Raw, Marker::Metadata>; N]>,
-    pub(crate) muls: SmallVec<[Multiplicative<Marker, Marker::Raw, Marker::Metadata>; N]>,
+    pub(crate) flats: Flats<Marker, N>,
+    pub(crate) adds: Additives<Marker, N>,
+    pub(crate) muls: Multiplicatives<Marker, N>,
+    pub(crate) floors: Floors<Marker, N>,
+    pub(crate) ceilings: Ceilings<Marker, N>,
+    pub(crate) overrides: Overrides<Marker, N>,
+    pub(crate) timers: SmallVec<[(ModifierId, Duration); N]>,
+    pub(crate) stacking: SmallVec<[(Marker::Metadata, Stacking); N]>,
+    pub(crate) conditions: BTreeMap<ModifierId, Condition>,
+    /// Results of the conditions and the unclamped value of the last [`Stat::evaluate_with`].
+    pub(crate) context_cache: Option<(SmallVec<[bool; N]>, Marker::Raw)>,
+    pub(crate) min: Option<Bound<Marker::Raw>>,
+    pub(crate) max: Option<Bound<Marker::Raw>>,
+    pub(crate) flat_curve: Option<Curve<Marker::Raw>>,
+    pub(crate) additive_curve: Option<Curve<Marker::Raw>>,
+    pub(crate) conversion: Option<Conversion<Marker::Raw>>,
+    pub(crate) observers: Observers<Marker::Raw, O>,
 }
 
-impl<Marker, const N: usize> Default for Stat<Marker, N>
+impl<Marker, const N: usize, O> Default for Stat<Marker, N, O>
 where
     Marker: StatMarker,
-    Marker::Raw: Default,
+    O: ?Sized,
 {
     fn default() -> Self {
         Self {
-            base: Default::default(),
-            cached: Some(Marker::Raw::default()),
+            base: Marker::Raw::ZERO,
+            cached: Some(Marker::Raw::ZERO),
             flats: Default::default(),
             adds: Default::default(),
             muls: Default::default(),
+            floors: Default::default(),
+            ceilings: Default::default(),
+            overrides: Default::default(),
+            timers: Default::default(),
+            stacking: Default::default(),
+            conditions: Default::default(),
+            context_cache: None,
+            min: None,
+            max: None,
+            flat_curve: None,
+            additive_curve: None,
+            conversion: None,
+            observers: Default::default(),
         }
     }
 }
 
-impl<Marker, const N: usize> Stat<Marker, N>
+impl<Marker, const N: usize, O> Stat<Marker, N, O>
 where
     Marker: StatMarker,
-    Flat<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Additive<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Multiplicative<Marker, Marker::Raw, Marker::Metadata>: Default,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
 {
-    pub fn with_base(base: Marker::Raw) -> Self
-    where
-        Self: Default,
-    {
+    pub fn with_base(base: Marker::Raw) -> Self {
         Self {
             base,
             cached: Some(base),
@@ -63,40 +134,329 @@ where
         self.base
     }
 
+    pub fn set_base(&mut self, base: Marker::Raw) -> &mut Self {
+        self.base = base;
+        self.invalidate();
+        self
+    }
+
+    /// Adds `amount` to the base value (e.g. stat growth on level up).
+    pub fn add_to_base(&mut self, amount: Marker::Raw) -> &mut Self {
+        self.set_base(self.base + amount)
+    }
+
+    /// Replaces the base value with the result of `f` applied to it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Strength;
+    ///
+    /// impl StatMarker for Strength {
+    ///     type Raw = i32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Strength>::with_base(10);
+    /// stat.apply_flat(Flat::from_raw(5));
+    ///
+    /// stat.map_base(|base| base * 3 / 2).add_to_base(1);
+    ///
+    /// assert_eq!(stat.base(), 16);
+    /// assert_eq!(stat.cache_value().cached(), Some(21));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn map_base(&mut self, f: impl FnOnce(Marker::Raw) -> Marker::Raw) -> &mut Self {
+        self.set_base(f(self.base))
+    }
+
+    /// Evaluates the stat, if it isn't cached already.
+    ///
+    /// Modifiers are grouped by their [stage][Modifier::order] and the stages are evaluated in
+    /// ascending order. Each stage computes `(value + flats) * adds * muls`, where `value` is the
+    /// result of the previous stage (or the base value for the first one) and totals of flats and
+    /// additives are mapped by their [curves][Stat::set_additive_curve]. Modifiers suppressed by
+    /// [stacking policies][Stat::set_stacking] are skipped. The result is then
+    /// limited by the strictest [floor][Floor] and [ceiling][Ceiling] and finally replaced by the
+    /// winning [override][Override], if any.
+    ///
+    /// [Conditional][Stat::apply_flat_if] modifiers are skipped, see [`Stat::evaluate_with`].
     pub fn cache_value(&mut self) -> &mut Self {
         if self.cached.is_none() {
-            let flats = self
-                .flats
-                .iter()
-                .fold(Flat::default().raw(), |acc: Marker::Raw, m| acc + m.raw());
-            let adds = self
-                .adds
-                .iter()
-                .fold(Additive::default().raw(), |acc: Marker::Raw, m| {
-                    acc + m.raw()
-                });
-            let muls = self
-                .muls
-                .iter()
-                .fold(Multiplicative::default().raw(), |acc: Marker::Raw, m| {
-                    acc * m.raw()
-                });
-            self.cached = Some((self.base + flats) * adds * muls);
+            let mask = self.condition_mask(None);
+            self.cached = Some(self.evaluate(&self.suppressed(&mask)));
         }
+        self.observers.notify(self.cached());
         self
     }
 
-    pub fn cached(&self) -> Option<Marker::Raw> {
-        self.cached
+    /// Evaluates the stat including [conditional][Stat::apply_flat_if] modifiers, whose
+    /// conditions hold for `context`, and returns its final value, clamped and
+    /// [converted][crate::derived::StatGraph::convert] like [`Stat::cached`].
+    ///
+    /// The result is cached until the stat changes or the conditions give different results, see
+    /// [`Stat::is_stale`]. The context-free [cached value][Stat::cached] is left untouched.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Damage;
+    ///
+    /// impl StatMarker for Damage {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// struct Combat {
+    ///     health: f32,
+    ///     night: bool,
+    /// }
+    ///
+    /// let mut stat = Stat::<Damage>::with_base(10.);
+    /// stat.apply_add_if(Additive::from_raw(0.2), |ctx: &Combat| ctx.health < 0.5);
+    /// stat.apply_flat_if(Flat::from_raw(3.), |ctx: &Combat| ctx.night);
+    ///
+    /// let mut ctx = Combat { health: 1., night: false };
+    /// assert_eq!(stat.evaluate_with(&ctx), 10.);
+    ///
+    /// ctx.health = 0.3;
+    /// assert!(stat.is_stale(&ctx));
+    /// assert_eq!(stat.evaluate_with(&ctx), 12.);
+    /// assert!(!stat.is_stale(&ctx));
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(10.));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_with<C: 'static>(&mut self, context: &C) -> Marker::Raw {
+        let mask = self.condition_mask(Some(context));
+        let value = match &self.context_cache {
+            Some((cached, value)) if *cached == mask => *value,
+            _ => {
+                let value = self.evaluate(&self.suppressed(&mask));
+                self.context_cache = Some((mask, value));
+                value
+            }
+        };
+        self.convert(self.clamp(value))
+    }
+
+    /// Checks whether the result of [`Stat::evaluate_with`] for `context` has to be recomputed,
+    /// because the stat changed or some conditions give different results.
+    pub fn is_stale<C: 'static>(&self, context: &C) -> bool {
+        match &self.context_cache {
+            Some((cached, _)) => *cached != self.condition_mask(Some(context)),
+            None => true,
+        }
+    }
+
+    /// Results of the conditions for `context`. Conditions never hold without a context or for a
+    /// context of a different type.
+    fn condition_mask(&self, context: Option<&dyn Any>) -> SmallVec<[bool; N]> {
+        self.conditions
+            .values()
+            .map(|condition| context.is_some_and(|context| condition(context)))
+            .collect()
+    }
+
+    /// Unclamped final value, skipping `suppressed` modifiers.
+    fn evaluate(&self, suppressed: &[ModifierId]) -> Marker::Raw {
+        let mut raws = StageRaws::new();
+        let value = self.stages().into_iter().fold(self.base, |value, stage| {
+            Marker::Formula::evaluate(value, &self.stage_modifiers(&mut raws, stage, suppressed))
+        });
+        let value = self.limit_ceiling(self.limit_floor(value, suppressed), suppressed);
+        self.active_override(suppressed)
+            .map_or(value, Modifier::raw)
+    }
+
+    /// Explains how the final value of the stat is computed, step by step.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Damage;
+    ///
+    /// impl StatMarker for Damage {
+    ///     type Raw = f32;
+    ///     type Metadata = &'static str;
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Damage>::with_base(10.);
+    /// stat.apply_flat(Flat::with_metadata(5., "sword"));
+    /// stat.apply_mul(Multiplicative::with_metadata(2., "rage"));
+    ///
+    /// let breakdown = stat.breakdown();
+    ///
+    /// assert_eq!(breakdown.stages[0].after_flats, 15.);
+    /// assert_eq!(breakdown.value, 30.);
+    /// println!("{breakdown}");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn breakdown(&self) -> Breakdown<Marker> {
+        let suppressed = self.suppressed(&self.condition_mask(None));
+        let mut value = self.base;
+        let mut raws = StageRaws::new();
+        let stages = self
+            .stages()
+            .into_iter()
+            .map(|stage| {
+                let input = value;
+                let mut modifiers = self.stage_modifiers(&mut raws, stage, &suppressed);
+                value = Marker::Formula::evaluate(input, &modifiers);
+                let additive_sum = modifiers.additive_sum();
+                modifiers.multiplicatives = &[];
+                let after_additives = Marker::Formula::evaluate(input, &modifiers);
+                modifiers.additives = &[];
+                let after_flats = Marker::Formula::evaluate(input, &modifiers);
+                let (flats, suppressed_flats) = split_stage(&self.flats, stage, &suppressed);
+                let (additives, suppressed_additives) = split_stage(&self.adds, stage, &suppressed);
+                let (multiplicatives, suppressed_multiplicatives) =
+                    split_stage(&self.muls, stage, &suppressed);
+                StageBreakdown {
+                    order: stage,
+                    input,
+                    flats,
+                    suppressed_flats,
+                    flat_sum: modifiers.flat_sum(),
+                    after_flats,
+                    additives,
+                    suppressed_additives,
+                    additive_sum,
+                    after_additives,
+                    multiplicatives,
+                    suppressed_multiplicatives,
+                    after_multiplicatives: value,
+                }
+            })
+            .collect();
+
+        let after_floors = self.limit_floor(value, &suppressed);
+        let after_ceilings = self.limit_ceiling(after_floors, &suppressed);
+        let active_override = self.active_override(&suppressed).copied();
+        let unclamped = active_override.map_or(after_ceilings, |m| m.raw());
+        Breakdown {
+            base: self.base,
+            stages,
+            after_stages: value,
+            floors: active(&self.floors, &suppressed).copied().collect(),
+            after_floors,
+            ceilings: active(&self.ceilings, &suppressed).copied().collect(),
+            after_ceilings,
+            overrides: active(&self.overrides, &suppressed).copied().collect(),
+            active_override,
+            unclamped,
+            unconverted: self.clamp(unclamped),
+            value: self.convert(self.clamp(unclamped)),
+        }
+    }
+
+    /// Handles of conditional modifiers, whose conditions don't hold according to `mask`, and of
+    /// flat, additive and multiplicative modifiers suppressed by stacking policies.
+    fn suppressed(&self, mask: &[bool]) -> SmallVec<[ModifierId; N]> {
+        let inactive: SmallVec<[ModifierId; N]> = self
+            .conditions
+            .iter()
+            .zip(mask)
+            .filter(|(_, holds)| !**holds)
+            .map(|((id, _), _)| *id)
+            .collect();
+        let mut suppressed = inactive.clone();
+        for &(metadata, stacking) in &self.stacking {
+            suppressed.extend(stacking.suppressed(grouped(&self.flats, metadata, &inactive)));
+            suppressed.extend(stacking.suppressed(grouped(&self.adds, metadata, &inactive)));
+            suppressed.extend(stacking.suppressed(grouped(&self.muls, metadata, &inactive)));
+        }
+        suppressed
+    }
+
+    /// Raises `value` to the highest floor.
+    fn limit_floor(&self, value: Marker::Raw, suppressed: &[ModifierId]) -> Marker::Raw {
+        active(&self.floors, suppressed)
+            .map(Modifier::raw)
+            .fold(
+                value,
+                |value, floor| if floor > value { floor } else { value },
+            )
+    }
+
+    /// Lowers `value` to the lowest ceiling.
+    fn limit_ceiling(&self, value: Marker::Raw, suppressed: &[ModifierId]) -> Marker::Raw {
+        active(&self.ceilings, suppressed)
+            .map(Modifier::raw)
+            .fold(value, |value, ceil| if ceil < value { ceil } else { value })
+    }
+
+    /// Override with the highest order, which was applied last among equal ones.
+    fn active_override(
+        &self,
+        suppressed: &[ModifierId],
+    ) -> Option<&Override<Marker, Marker::Raw, Marker::Metadata>> {
+        active(&self.overrides, suppressed).max_by_key(|m| m.order())
     }
 
-    pub fn apply_flat(&mut self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) -> &mut Self {
+    /// Indexes of the stages with at least one modifier in ascending order.
+    fn stages(&self) -> SmallVec<[isize; N]> {
+        let mut stages: SmallVec<[isize; N]> = self
+            .flats
+            .iter()
+            .map(Modifier::order)
+            .chain(self.adds.iter().map(Modifier::order))
+            .chain(self.muls.iter().map(Modifier::order))
+            .collect();
+        stages.sort_unstable();
+        stages.dedup();
+        stages
+    }
+
+    /// Collects raw values of the modifiers of `stage`, which apply, into `raws`.
+    fn stage_modifiers<'a>(
+        &'a self,
+        raws: &'a mut StageRaws<Marker::Raw, N>,
+        stage: isize,
+        suppressed: &[ModifierId],
+    ) -> StageModifiers<'a, Marker::Raw> {
+        collect_raws(&mut raws.flats, &self.flats, stage, suppressed);
+        collect_raws(&mut raws.additives, &self.adds, stage, suppressed);
+        collect_raws(&mut raws.multiplicatives, &self.muls, stage, suppressed);
+        StageModifiers {
+            flats: &raws.flats,
+            additives: &raws.additives,
+            multiplicatives: &raws.multiplicatives,
+            flat_curve: self.flat_curve.as_ref(),
+            additive_curve: self.additive_curve.as_ref(),
+        }
+    }
+
+    pub fn apply_flat(
+        &mut self,
+        mut flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        flat.id = Some(id);
         self.flats.push(flat);
-        self.cached = None;
-        self
+        self.invalidate();
+        id
     }
 
-    pub fn apply_flat_from_shared<T>(&mut self, flat: T) -> &mut Self
+    pub fn apply_flat_from_shared<T>(&mut self, flat: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Flat<Marker, Marker::Raw, Marker::Metadata>>,
     {
@@ -105,14 +465,16 @@ where
 
     pub fn apply_add(
         &mut self,
-        additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
-    ) -> &mut Self {
+        mut additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        additive.id = Some(id);
         self.adds.push(additive);
-        self.cached = None;
-        self
+        self.invalidate();
+        id
     }
 
-    pub fn apply_add_from_shared<T>(&mut self, additive: T) -> &mut Self
+    pub fn apply_add_from_shared<T>(&mut self, additive: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Additive<Marker, Marker::Raw, Marker::Metadata>>,
     {
@@ -121,24 +483,365 @@ where
 
     pub fn apply_mul(
         &mut self,
-        multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
-    ) -> &mut Self {
+        mut multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        multiplicative.id = Some(id);
         self.muls.push(multiplicative);
-        self.cached = None;
-        self
+        self.invalidate();
+        id
     }
 
-    pub fn apply_mul_from_shared<T>(&mut self, multiplicative: T) -> &mut Self
+    pub fn apply_mul_from_shared<T>(&mut self, multiplicative: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
     {
         self.apply_mul(multiplicative.share())
     }
 
+    /// Applies a floor, limiting the final value of the stat from below.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct AttackSpeed;
+    ///
+    /// impl StatMarker for AttackSpeed {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<AttackSpeed>::with_base(1.);
+    /// stat.apply_mul(Multiplicative::from_raw(0.25));
+    ///
+    /// let boots = stat.apply_floor(Floor::from_raw(0.5));
+    /// stat.apply_floor(Floor::from_raw(0.4));
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(0.5));
+    ///
+    /// stat.remove_by_id(boots);
+    /// assert_eq!(stat.cache_value().cached(), Some(0.4));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn apply_floor(
+        &mut self,
+        mut floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        floor.id = Some(id);
+        self.floors.push(floor);
+        self.invalidate();
+        id
+    }
+
+    pub fn apply_floor_from_shared<T>(&mut self, floor: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.apply_floor(floor.share())
+    }
+
+    /// Applies a ceiling, limiting the final value of the stat from above.
+    pub fn apply_ceiling(
+        &mut self,
+        mut ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        ceiling.id = Some(id);
+        self.ceilings.push(ceiling);
+        self.invalidate();
+        id
+    }
+
+    pub fn apply_ceiling_from_shared<T>(&mut self, ceiling: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.apply_ceiling(ceiling.share())
+    }
+
+    /// Applies an override, replacing the final value of the stat.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Speed;
+    ///
+    /// impl StatMarker for Speed {
+    ///     type Raw = f32;
+    ///     type Metadata = &'static str;
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Speed>::with_base(5.);
+    /// stat.apply_mul(Multiplicative::from_raw(2.));
+    ///
+    /// let petrify = stat.apply_override(Override::with_metadata(0., "petrify"));
+    /// stat.apply_override(Override::with_metadata(1., "slow"));
+    /// stat.apply_override(Override::with_metadata(20., "blink").at::<Last>());
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(20.));
+    ///
+    /// stat.remove_with_metadata("blink");
+    /// assert_eq!(stat.cache_value().cached(), Some(1.));
+    ///
+    /// stat.remove_with_metadata("slow").remove_by_id(petrify);
+    /// assert_eq!(stat.cache_value().cached(), Some(10.));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn apply_override(
+        &mut self,
+        mut modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        modifier.id = Some(id);
+        self.overrides.push(modifier);
+        self.invalidate();
+        id
+    }
+
+    pub fn apply_override_from_shared<T>(&mut self, modifier: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Override<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.apply_override(modifier.share())
+    }
+
+    /// Applies a flat modifier, which gets removed by [`Stat::tick`] once `duration` expires.
+    pub fn apply_flat_timed(
+        &mut self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_flat(flat);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies an additive modifier, which gets removed by [`Stat::tick`] once `duration` expires.
+    pub fn apply_add_timed(
+        &mut self,
+        additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_add(additive);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies a multiplicative modifier, which gets removed by [`Stat::tick`] once `duration`
+    /// expires.
+    pub fn apply_mul_timed(
+        &mut self,
+        multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_mul(multiplicative);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies a floor, which gets removed by [`Stat::tick`] once `duration` expires.
+    pub fn apply_floor_timed(
+        &mut self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_floor(floor);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies a ceiling, which gets removed by [`Stat::tick`] once `duration` expires.
+    pub fn apply_ceiling_timed(
+        &mut self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_ceiling(ceiling);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies an override, which gets removed by [`Stat::tick`] once `duration` expires.
+    pub fn apply_override_timed(
+        &mut self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        let id = self.apply_override(modifier);
+        self.timers.push((id, duration));
+        id
+    }
+
+    /// Applies a flat modifier, which only applies while `condition` holds for the context passed
+    /// to [`Stat::evaluate_with`].
+    pub fn apply_flat_if<C: 'static>(
+        &mut self,
+        mut flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        flat.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.flats.push(flat);
+        self.invalidate();
+        id
+    }
+
+    /// Applies an additive modifier, which only applies while `condition` holds, see [`Stat::apply_flat_if`].
+    pub fn apply_add_if<C: 'static>(
+        &mut self,
+        mut additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        additive.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.adds.push(additive);
+        self.invalidate();
+        id
+    }
+
+    /// Applies a multiplicative modifier, which only applies while `condition` holds, see [`Stat::apply_flat_if`].
+    pub fn apply_mul_if<C: 'static>(
+        &mut self,
+        mut multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        multiplicative.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.muls.push(multiplicative);
+        self.invalidate();
+        id
+    }
+
+    /// Applies a floor, which only applies while `condition` holds, see [`Stat::apply_flat_if`].
+    pub fn apply_floor_if<C: 'static>(
+        &mut self,
+        mut floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        floor.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.floors.push(floor);
+        self.invalidate();
+        id
+    }
+
+    /// Applies a ceiling, which only applies while `condition` holds, see [`Stat::apply_flat_if`].
+    pub fn apply_ceiling_if<C: 'static>(
+        &mut self,
+        mut ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        ceiling.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.ceilings.push(ceiling);
+        self.invalidate();
+        id
+    }
+
+    /// Applies an override, which only applies while `condition` holds, see [`Stat::apply_flat_if`].
+    pub fn apply_override_if<C: 'static>(
+        &mut self,
+        mut modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        let id = ModifierId::next();
+        modifier.id = Some(id);
+        self.conditions.insert(id, erase(condition));
+        self.overrides.push(modifier);
+        self.invalidate();
+        id
+    }
+
+    /// Advances durations of all timed modifiers by `delta` and removes the expired ones.
+    ///
+    /// Returns handles of the expired modifiers.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::{prelude::*, timed::{Duration, Tick}};
+    ///
+    /// struct Speed;
+    ///
+    /// impl StatMarker for Speed {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Speed>::with_base(1.);
+    /// let haste = stat.apply_mul_timed(Multiplicative::from_raw(2.), Duration::Seconds(5.));
+    ///
+    /// assert!(stat.tick(Tick::Seconds(3.)).is_empty());
+    /// assert_eq!(stat.cache_value().cached(), Some(2.));
+    ///
+    /// assert_eq!(stat.tick(Tick::Seconds(3.)).as_slice(), &[haste]);
+    /// assert_eq!(stat.cache_value().cached(), Some(1.));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn tick(&mut self, delta: Tick) -> SmallVec<[ModifierId; N]> {
+        let mut expired = SmallVec::new();
+        let mut i = 0;
+        while i < self.timers.len() {
+            let (id, duration) = &mut self.timers[i];
+            let id = *id;
+            if !duration.advance(delta) && self.contains(id) {
+                i += 1;
+                continue;
+            }
+            self.timers.swap_remove(i);
+            if self.contains(id) {
+                self.remove_by_id(id);
+                expired.push(id);
+            }
+        }
+        expired
+    }
+
+    /// Returns remaining duration of a timed modifier.
+    pub fn remaining(&self, id: ModifierId) -> Option<Duration> {
+        self.timers
+            .iter()
+            .find(|(timer, _)| *timer == id)
+            .map(|(_, duration)| *duration)
+    }
+
+    /// Checks whether modifier with handle `id` is applied to the stat.
+    pub fn contains(&self, id: ModifierId) -> bool {
+        self.flats.iter().any(|m| m.id == Some(id))
+            || self.adds.iter().any(|m| m.id == Some(id))
+            || self.muls.iter().any(|m| m.id == Some(id))
+            || self.floors.iter().any(|m| m.id == Some(id))
+            || self.ceilings.iter().any(|m| m.id == Some(id))
+            || self.overrides.iter().any(|m| m.id == Some(id))
+    }
+
     pub fn remove_flat(&mut self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) -> &mut Self {
         if let Some(i) = self.flats.iter().position(|&v| v == flat) {
-            self.flats.swap_remove(i);
-            self.cached = None;
+            let removed = self.flats.swap_remove(i);
+            self.forget(removed.id);
+            self.invalidate();
         }
         self
     }
@@ -148,8 +851,9 @@ where
         additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
     ) -> &mut Self {
         if let Some(i) = self.adds.iter().position(|&v| v == additive) {
-            self.adds.swap_remove(i);
-            self.cached = None;
+            let removed = self.adds.swap_remove(i);
+            self.forget(removed.id);
+            self.invalidate();
         }
         self
     }
@@ -159,37 +863,658 @@ where
         multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
     ) -> &mut Self {
         if let Some(i) = self.muls.iter().position(|&v| v == multiplicative) {
+            let removed = self.muls.swap_remove(i);
+            self.forget(removed.id);
+            self.invalidate();
+        }
+        self
+    }
+
+    pub fn remove_floor(
+        &mut self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> &mut Self {
+        if let Some(i) = self.floors.iter().position(|&v| v == floor) {
+            let removed = self.floors.swap_remove(i);
+            self.forget(removed.id);
+            self.invalidate();
+        }
+        self
+    }
+
+    pub fn remove_ceiling(
+        &mut self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> &mut Self {
+        if let Some(i) = self.ceilings.iter().position(|&v| v == ceiling) {
+            let removed = self.ceilings.swap_remove(i);
+            self.forget(removed.id);
+            self.invalidate();
+        }
+        self
+    }
+
+    pub fn remove_override(
+        &mut self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> &mut Self {
+        // Application order of overrides resolves ties, so it has to be preserved.
+        if let Some(i) = self.overrides.iter().position(|&v| v == modifier) {
+            let removed = self.overrides.remove(i);
+            self.forget(removed.id);
+            self.invalidate();
+        }
+        self
+    }
+
+    /// Removes exactly the modifier with handle `id`, returned from one of `apply_*` methods.
+    pub fn remove_by_id(&mut self, id: ModifierId) -> &mut Self {
+        if let Some(i) = self.flats.iter().position(|m| m.id == Some(id)) {
+            self.flats.swap_remove(i);
+        } else if let Some(i) = self.adds.iter().position(|m| m.id == Some(id)) {
+            self.adds.swap_remove(i);
+        } else if let Some(i) = self.muls.iter().position(|m| m.id == Some(id)) {
             self.muls.swap_remove(i);
-            self.cached = None;
+        } else if let Some(i) = self.floors.iter().position(|m| m.id == Some(id)) {
+            self.floors.swap_remove(i);
+        } else if let Some(i) = self.ceilings.iter().position(|m| m.id == Some(id)) {
+            self.ceilings.swap_remove(i);
+        } else if let Some(i) = self.overrides.iter().position(|m| m.id == Some(id)) {
+            self.overrides.remove(i);
+        } else {
+            return self;
+        }
+        self.forget(Some(id));
+        self.invalidate();
+        self
+    }
+
+    /// Removes every modifier with attached `metadata` (e.g. all bonuses of an unequipped item).
+    pub fn remove_with_metadata(&mut self, metadata: Marker::Metadata) -> &mut Self {
+        let removed: SmallVec<[Option<ModifierId>; N]> = with_metadata(&self.flats, metadata)
+            .chain(with_metadata(&self.adds, metadata))
+            .chain(with_metadata(&self.muls, metadata))
+            .chain(with_metadata(&self.floors, metadata))
+            .chain(with_metadata(&self.ceilings, metadata))
+            .chain(with_metadata(&self.overrides, metadata))
+            .collect();
+        if removed.is_empty() {
+            return self;
+        }
+        for id in removed {
+            self.forget(id);
         }
+        self.flats.retain(|m| m.metadata() != Some(metadata));
+        self.adds.retain(|m| m.metadata() != Some(metadata));
+        self.muls.retain(|m| m.metadata() != Some(metadata));
+        self.floors.retain(|m| m.metadata() != Some(metadata));
+        self.ceilings.retain(|m| m.metadata() != Some(metadata));
+        self.overrides.retain(|m| m.metadata() != Some(metadata));
+        self.invalidate();
         self
     }
 
-    pub fn flats(
+    pub fn flats_with_metadata(
+        &self,
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Flat<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.flats
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    pub fn additives_with_metadata(
+        &self,
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Additive<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.adds
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    pub fn multiplicatives_with_metadata(
+        &self,
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Multiplicative<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.muls
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    pub fn floors_with_metadata(
+        &self,
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Floor<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.floors
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    pub fn ceilings_with_metadata(
         &self,
-    ) -> &SmallVec<[Flat<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N]>
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Ceiling<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.ceilings
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    pub fn overrides_with_metadata(
+        &self,
+        metadata: Marker::Metadata,
+    ) -> impl Iterator<Item = &Override<Marker, Marker::Raw, Marker::Metadata>> + '_ {
+        self.overrides
+            .iter()
+            .filter(move |m| m.metadata() == Some(metadata))
+    }
+
+    /// Sets how flat, additive and multiplicative modifiers with attached `metadata` stack with
+    /// other modifiers of the same kind and metadata, regardless of their stage.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Speed;
+    ///
+    /// impl StatMarker for Speed {
+    ///     type Raw = f32;
+    ///     type Metadata = &'static str;
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Speed>::with_base(10.);
+    /// stat.set_stacking("slow", Stacking::LowestOnly);
+    /// stat.set_stacking("haste", Stacking::MaxStacks(2));
+    ///
+    /// stat.apply_mul(Multiplicative::with_metadata(0.8, "slow"));
+    /// stat.apply_mul(Multiplicative::with_metadata(0.5, "slow"));
+    /// for _ in 0..3 {
+    ///     stat.apply_flat(Flat::with_metadata(1., "haste"));
+    /// }
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(6.));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn set_stacking(&mut self, metadata: Marker::Metadata, stacking: Stacking) -> &mut Self {
+        self.stacking.retain(|(m, _)| *m != metadata);
+        if stacking != Stacking::Stack {
+            self.stacking.push((metadata, stacking));
+        }
+        self.invalidate();
+        self
+    }
+
+    /// Stacking policy of modifiers with attached `metadata`, see [`Stat::set_stacking`].
+    pub fn stacking(&self, metadata: Marker::Metadata) -> Stacking {
+        self.stacking
+            .iter()
+            .find(|(m, _)| *m == metadata)
+            .map_or(Stacking::Stack, |(_, stacking)| *stacking)
+    }
+
+    /// Subscribes `observer`, called with the old and the new final value whenever a change of
+    /// the stat (e.g. applying or removing a modifier) changes its final value.
+    ///
+    /// While the stat has observers, it gets evaluated eagerly after every change. Observers must be
+    /// [`Send`] and [`Sync`], unless the stat takes [local observers][observer::LocalCallback].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::sync::mpsc;
+    ///
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Health;
+    ///
+    /// impl StatMarker for Health {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut stat = Stat::<Health>::with_base(100.);
+    /// let observer = stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+    ///
+    /// let buff = stat.apply_mul(Multiplicative::from_raw(2.));
+    /// stat.remove_by_id(buff);
+    /// stat.unsubscribe(observer);
+    /// stat.apply_flat(Flat::from_raw(1.));
+    ///
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(100., 200.), (200., 100.)]);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe<F>(&mut self, observer: F) -> ObserverId
+    where
+        F: FnMut(Marker::Raw, Marker::Raw) + IntoCallback<O>,
     {
+        self.cache_value();
+        let value = self.cached();
+        self.observers.subscribe(value, observer.into_callback())
+    }
+
+    /// Removes the observer with handle `id`, returned from [`Stat::subscribe`].
+    pub fn unsubscribe(&mut self, id: ObserverId) -> &mut Self {
+        self.observers.unsubscribe(id);
+        self
+    }
+
+    /// Makes all changes done by `f` at once: [observers][Stat::subscribe] get notified at most
+    /// once, after `f` returns. If `f` returns an error or panics, the stat is rolled back to its
+    /// state before the batch, and observers aren't notified during the unwinding.
+    ///
+    /// The rollback needs a snapshot, so every batch [clones][Clone] the stat with all its
+    /// modifiers up front.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::sync::mpsc;
+    ///
+    /// use mini_stat::prelude::*;
+    ///
+    /// struct Armor;
+    ///
+    /// impl StatMarker for Armor {
+    ///     type Raw = f32;
+    ///     type Metadata = &'static str;
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut stat = Stat::<Armor>::with_base(10.);
+    /// stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+    ///
+    /// stat.batch(|stat| {
+    ///     stat.apply_flat(Flat::with_metadata(5., "helmet"));
+    ///     stat.apply_mul(Multiplicative::with_metadata(2., "helmet"));
+    ///     Ok::<_, ()>(())
+    /// })
+    /// .unwrap();
+    ///
+    /// let failed = stat.batch(|stat| {
+    ///     stat.remove_with_metadata("helmet");
+    ///     Err::<(), _>("helmet is cursed")
+    /// });
+    ///
+    /// assert_eq!(failed, Err("helmet is cursed"));
+    /// assert_eq!(stat.cache_value().cached(), Some(30.));
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(10., 30.)]);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn batch<T, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, E>) -> Result<T, E> {
+        let paused = self.observers.pause();
+        let mut batch = Batch {
+            snapshot: Some(self.clone()),
+            stat: self,
+            paused,
+        };
+        let result = f(batch.stat);
+        if result.is_ok() {
+            batch.snapshot = None;
+        }
+        result
+    }
+
+    pub fn flats(&self) -> &Flats<Marker, N> {
         &self.flats
     }
 
-    pub fn additives(
-        &self,
-    ) -> &SmallVec<
-        [Additive<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N],
-    > {
+    pub fn additives(&self) -> &Additives<Marker, N> {
         &self.adds
     }
 
-    pub fn multiplicatives(
-        &self,
-    ) -> &SmallVec<
-        [Multiplicative<Marker, <Marker as StatMarker>::Raw, <Marker as StatMarker>::Metadata>; N],
-    > {
+    pub fn multiplicatives(&self) -> &Multiplicatives<Marker, N> {
         &self.muls
     }
+
+    pub fn floors(&self) -> &Floors<Marker, N> {
+        &self.floors
+    }
+
+    pub fn ceilings(&self) -> &Ceilings<Marker, N> {
+        &self.ceilings
+    }
+
+    pub fn overrides(&self) -> &Overrides<Marker, N> {
+        &self.overrides
+    }
+}
+
+/// Handles of `modifiers` with attached `metadata`.
+fn with_metadata<T>(
+    modifiers: &[T],
+    metadata: T::Metadata,
+) -> impl Iterator<Item = Option<ModifierId>> + '_
+where
+    T: Modifier,
+    T::Metadata: PartialEq,
+{
+    modifiers
+        .iter()
+        .filter(move |m| m.metadata() == Some(metadata))
+        .map(Modifier::id)
+}
+
+impl<Marker, const N: usize, O> Stat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+{
+    /// Final value of the stat, [clamped][Stat::set_min] to its bounds.
+    ///
+    /// Returns [`None`] if the stat wasn't [cached][Stat::cache_value] since the last change.
+    /// [Dynamic bounds][Bound::Dynamic] are read on each call, so the result may change without
+    /// notifying observers; [`Stat::cache_value`] notifies them of the newly clamped value.
+    pub fn cached(&self) -> Option<Marker::Raw> {
+        self.unconverted().map(|value| self.convert(value))
+    }
+
+    /// Final value of the stat, before parts of it were
+    /// [converted][crate::derived::StatGraph::convert] to other stats.
+    pub(crate) fn unconverted(&self) -> Option<Marker::Raw> {
+        self.cached.map(|value| self.clamp(value))
+    }
+
+    pub(crate) fn set_conversion(&mut self, conversion: Conversion<Marker::Raw>) {
+        self.conversion = Some(conversion);
+        self.invalidate();
+    }
+
+    /// Final value of the stat before clamping it to its bounds.
+    pub fn unclamped(&self) -> Option<Marker::Raw> {
+        self.cached
+    }
+
+    /// Checks whether the final value of the stat was clamped to one of its bounds.
+    pub fn is_clamped(&self) -> bool {
+        self.cached.is_some_and(|value| self.clamp(value) != value)
+    }
+
+    /// Sets lower bound of the final value.
+    pub fn set_min(&mut self, min: impl Into<Bound<Marker::Raw>>) -> &mut Self {
+        self.min = Some(min.into());
+        self.observers.notify(self.cached());
+        self
+    }
+
+    /// Sets upper bound of the final value. Upper bound takes precedence over the lower one.
+    pub fn set_max(&mut self, max: impl Into<Bound<Marker::Raw>>) -> &mut Self {
+        self.max = Some(max.into());
+        self.observers.notify(self.cached());
+        self
+    }
+
+    /// Sets curve mapping the total of flat modifiers of each stage.
+    pub fn set_flat_curve(&mut self, curve: Curve<Marker::Raw>) -> &mut Self {
+        self.flat_curve = Some(curve);
+        self.invalidate();
+        self
+    }
+
+    /// Sets curve mapping the total of additive modifiers of each stage, before one is added to
+    /// it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mini_stat::{curve::Curve, prelude::*};
+    ///
+    /// struct Armor;
+    ///
+    /// impl StatMarker for Armor {
+    ///     type Raw = f32;
+    ///     type Metadata = ();
+    ///     type Formula = Standard;
+    /// }
+    ///
+    /// let mut stat = Stat::<Armor>::with_base(10.);
+    /// stat.set_additive_curve(Curve::hyperbolic(1.));
+    /// stat.apply_add(Additive::from_raw(1.));
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(15.));
+    ///
+    /// stat.apply_add(Additive::from_raw(2.));
+    ///
+    /// assert_eq!(stat.cache_value().cached(), Some(17.5));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn set_additive_curve(&mut self, curve: Curve<Marker::Raw>) -> &mut Self {
+        self.additive_curve = Some(curve);
+        self.invalidate();
+        self
+    }
+
+    pub fn clear_curves(&mut self) -> &mut Self {
+        self.flat_curve = None;
+        self.additive_curve = None;
+        self.invalidate();
+        self
+    }
+
+    pub fn flat_curve(&self) -> Option<&Curve<Marker::Raw>> {
+        self.flat_curve.as_ref()
+    }
+
+    pub fn additive_curve(&self) -> Option<&Curve<Marker::Raw>> {
+        self.additive_curve.as_ref()
+    }
+
+    pub fn clear_bounds(&mut self) -> &mut Self {
+        self.min = None;
+        self.max = None;
+        self.observers.notify(self.cached());
+        self
+    }
+
+    pub fn min(&self) -> Option<&Bound<Marker::Raw>> {
+        self.min.as_ref()
+    }
+
+    pub fn max(&self) -> Option<&Bound<Marker::Raw>> {
+        self.max.as_ref()
+    }
+
+    /// Drops the timer and the condition of the removed modifier with handle `id`.
+    fn forget(&mut self, id: Option<ModifierId>) {
+        let Some(id) = id else {
+            return;
+        };
+        self.timers.retain(|(timer, _)| *timer != id);
+        self.conditions.remove(&id);
+    }
+
+    /// Drops the cached value. Stats with observers get evaluated right away, so the observers
+    /// are notified about the change.
+    fn invalidate(&mut self) {
+        self.cached = None;
+        self.context_cache = None;
+        if self.observers.is_listening() {
+            self.cache_value();
+        }
+    }
+
+    fn convert(&self, value: Marker::Raw) -> Marker::Raw {
+        self.conversion
+            .as_ref()
+            .map_or(value, |conversion| conversion(value))
+    }
+
+    fn clamp(&self, mut value: Marker::Raw) -> Marker::Raw {
+        if let Some(min) = self.min.as_ref().map(Bound::get) {
+            if value < min {
+                value = min;
+            }
+        }
+        if let Some(max) = self.max.as_ref().map(Bound::get) {
+            if value > max {
+                value = max;
+            }
+        }
+        value
+    }
+}
+
+/// Checks whether `modifier` belongs to `stage` and isn't suppressed.
+fn applies<T: Modifier>(modifier: &T, stage: isize, suppressed: &[ModifierId]) -> bool {
+    modifier.order() == stage && !is_suppressed(modifier, suppressed)
+}
+
+fn is_suppressed<T: Modifier>(modifier: &T, suppressed: &[ModifierId]) -> bool {
+    modifier.id().is_some_and(|id| suppressed.contains(&id))
+}
+
+/// `modifiers`, which aren't suppressed.
+fn active<'a: 'b, 'b, T: Modifier>(
+    modifiers: &'a [T],
+    suppressed: &'b [ModifierId],
+) -> impl Iterator<Item = &'a T> + 'b {
+    modifiers.iter().filter(|m| !is_suppressed(*m, suppressed))
+}
+
+fn erase<C: 'static>(condition: impl Fn(&C) -> bool + Send + Sync + 'static) -> Condition {
+    Arc::new(move |context: &dyn Any| context.downcast_ref().is_some_and(&condition))
+}
+
+/// Modifiers of `stage`, split into the applied and the suppressed ones.
+fn split_stage<T: Modifier + Copy>(
+    modifiers: &[T],
+    stage: isize,
+    suppressed: &[ModifierId],
+) -> (Vec<T>, Vec<T>) {
+    modifiers
+        .iter()
+        .filter(|m| m.order() == stage)
+        .partition(|m| applies(*m, stage, suppressed))
+}
+
+/// Raw values of the modifiers of the stage being evaluated, kept between stages to reuse their
+/// buffers.
+struct StageRaws<R, const N: usize> {
+    flats: SmallVec<[R; N]>,
+    additives: SmallVec<[R; N]>,
+    multiplicatives: SmallVec<[R; N]>,
+}
+
+impl<R, const N: usize> StageRaws<R, N> {
+    fn new() -> Self {
+        Self {
+            flats: SmallVec::new(),
+            additives: SmallVec::new(),
+            multiplicatives: SmallVec::new(),
+        }
+    }
+}
+
+/// Replaces `raws` by raw values of the modifiers of `stage`, which apply.
+fn collect_raws<T: Modifier, const N: usize>(
+    raws: &mut SmallVec<[T::Raw; N]>,
+    modifiers: &[T],
+    stage: isize,
+    suppressed: &[ModifierId],
+) {
+    raws.clear();
+    raws.extend(
+        modifiers
+            .iter()
+            .filter(|m| applies(*m, stage, suppressed))
+            .map(Modifier::raw),
+    );
+}
+
+/// Batch in progress, see [`Stat::batch`]. Rolls the stat back to `snapshot` and resumes its
+/// observers when dropped, also if the batch panics.
+struct Batch<'a, Marker, const N: usize, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+{
+    stat: &'a mut Stat<Marker, N, O>,
+    snapshot: Option<Stat<Marker, N, O>>,
+    paused: bool,
+}
+
+impl<Marker, const N: usize, O> Drop for Batch<'_, Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+{
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            let observers = mem::take(&mut self.stat.observers);
+            *self.stat = snapshot;
+            self.stat.observers = observers;
+        }
+        self.stat.observers.resume(self.paused);
+        if !thread::panicking() && self.stat.observers.is_listening() {
+            self.stat.cache_value();
+        }
+    }
+}
+
+/// Handles and values of `modifiers` with attached `metadata`, which aren't `inactive`.
+fn grouped<T>(
+    modifiers: &[T],
+    metadata: T::Metadata,
+    inactive: &[ModifierId],
+) -> Vec<(ModifierId, T::Raw)>
+where
+    T: Modifier,
+    T::Metadata: PartialEq,
+{
+    active(modifiers, inactive)
+        .filter(|m| m.metadata() == Some(metadata))
+        .filter_map(|m| Some((m.id()?, m.raw())))
+        .collect()
+}
+
+impl<Marker, const N: usize, O> Debug for Stat<Marker, N, O>
+where
+    Marker: StatMarker + Debug,
+    O: ?Sized,
+    Marker::Raw: Debug,
+    Marker::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stat")
+            .field("base", &self.base)
+            .field("cached", &self.cached)
+            .field("flats", &self.flats)
+            .field("adds", &self.adds)
+            .field("muls", &self.muls)
+            .field("floors", &self.floors)
+            .field("ceilings", &self.ceilings)
+            .field("overrides", &self.overrides)
+            .field("timers", &self.timers)
+            .field("stacking", &self.stacking)
+            .field("conditions", &self.conditions.len())
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("flat_curve", &self.flat_curve)
+            .field("additive_curve", &self.additive_curve)
+            .field("converted", &self.conversion.is_some())
+            .field("observers", &self.observers.len())
+            .finish()
+    }
 }
 
-impl<Marker: StatMarker, const N: usize> Clone for Stat<Marker, N> {
+/// Observers are not cloned.
+impl<Marker: StatMarker, const N: usize, O: ?Sized> Clone for Stat<Marker, N, O> {
     fn clone(&self) -> Self {
         Self {
             base: self.base,
@@ -197,6 +1522,19 @@ impl<Marker: StatMarker, const N: usize> Clone for Stat<Marker, N> {
             flats: self.flats.clone(),
             adds: self.adds.clone(),
             muls: self.muls.clone(),
+            floors: self.floors.clone(),
+            ceilings: self.ceilings.clone(),
+            overrides: self.overrides.clone(),
+            timers: self.timers.clone(),
+            stacking: self.stacking.clone(),
+            conditions: self.conditions.clone(),
+            context_cache: self.context_cache.clone(),
+            min: self.min.clone(),
+            max: self.max.clone(),
+            flat_curve: self.flat_curve.clone(),
+            additive_curve: self.additive_curve.clone(),
+            conversion: self.conversion.clone(),
+            observers: Default::default(),
         }
     }
 }
@@ -0,0 +1,233 @@
+use std::fmt::{self, Debug, Display};
+
+use crate::prelude::{
+    Additive, Ceiling, Flat, Floor, Modifier, Multiplicative, Override, StatMarker, StatNumber,
+};
+
+/// Step by step explanation of the final value of a [`Stat`][crate::stat::Stat].
+///
+/// See [`Stat::breakdown`][crate::stat::Stat::breakdown].
+pub struct Breakdown<Marker>
+where
+    Marker: StatMarker,
+{
+    /// Base value of the stat.
+    pub base: Marker::Raw,
+    /// Evaluated stages in ascending order.
+    pub stages: Vec<StageBreakdown<Marker>>,
+    /// Result of the last stage or base value of the stat.
+    pub after_stages: Marker::Raw,
+    pub floors: Vec<Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Value after raising it to the highest floor.
+    pub after_floors: Marker::Raw,
+    pub ceilings: Vec<Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Value after lowering it to the lowest ceiling.
+    pub after_ceilings: Marker::Raw,
+    /// All overrides applied to the stat.
+    pub overrides: Vec<Override<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Override replacing the result of the stages, if any.
+    pub active_override: Option<Override<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Final value before clamping it to the bounds of the stat.
+    pub unclamped: Marker::Raw,
+    /// Final value before parts of it were [converted][crate::derived::StatGraph::convert] to
+    /// other stats.
+    pub unconverted: Marker::Raw,
+    /// Final value of the stat.
+    pub value: Marker::Raw,
+}
+
+/// Explanation of a single evaluation [stage][Modifier::order].
+pub struct StageBreakdown<Marker>
+where
+    Marker: StatMarker,
+{
+    /// Index of the stage.
+    pub order: isize,
+    /// Result of the previous stage or base value of the stat.
+    pub input: Marker::Raw,
+    pub flats: Vec<Flat<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Flats suppressed by [stacking policies][crate::stat::Stat::set_stacking].
+    pub suppressed_flats: Vec<Flat<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Sum of the flats, mapped by the [flat curve][crate::stat::Stat::set_flat_curve], as
+    /// passed to the [formula][StatMarker::Formula].
+    pub flat_sum: Marker::Raw,
+    /// Result of the [formula][StatMarker::Formula] with only the flats applied.
+    pub after_flats: Marker::Raw,
+    pub additives: Vec<Additive<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Additives suppressed by [stacking policies][crate::stat::Stat::set_stacking].
+    pub suppressed_additives: Vec<Additive<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Sum of the additives, mapped by the
+    /// [additive curve][crate::stat::Stat::set_additive_curve], as passed to the
+    /// [formula][StatMarker::Formula]. How it affects the value depends on the formula, e.g.
+    /// [`Standard`][crate::formula::Standard] multiplies by one plus the sum.
+    pub additive_sum: Marker::Raw,
+    /// Result of the [formula][StatMarker::Formula] with the flats and the additives applied.
+    pub after_additives: Marker::Raw,
+    pub multiplicatives: Vec<Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Multiplicatives suppressed by [stacking policies][crate::stat::Stat::set_stacking].
+    pub suppressed_multiplicatives: Vec<Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
+    /// Result of the [formula][StatMarker::Formula] with all modifiers applied, i.e. the result of
+    /// the stage.
+    pub after_multiplicatives: Marker::Raw,
+}
+
+impl<Marker> Debug for Breakdown<Marker>
+where
+    Marker: StatMarker + Debug,
+    Marker::Raw: Debug,
+    Marker::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Breakdown")
+            .field("base", &self.base)
+            .field("stages", &self.stages)
+            .field("after_stages", &self.after_stages)
+            .field("floors", &self.floors)
+            .field("after_floors", &self.after_floors)
+            .field("ceilings", &self.ceilings)
+            .field("after_ceilings", &self.after_ceilings)
+            .field("overrides", &self.overrides)
+            .field("active_override", &self.active_override)
+            .field("unclamped", &self.unclamped)
+            .field("unconverted", &self.unconverted)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<Marker> Debug for StageBreakdown<Marker>
+where
+    Marker: StatMarker + Debug,
+    Marker::Raw: Debug,
+    Marker::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StageBreakdown")
+            .field("order", &self.order)
+            .field("input", &self.input)
+            .field("flats", &self.flats)
+            .field("suppressed_flats", &self.suppressed_flats)
+            .field("flat_sum", &self.flat_sum)
+            .field("after_flats", &self.after_flats)
+            .field("additives", &self.additives)
+            .field("suppressed_additives", &self.suppressed_additives)
+            .field("additive_sum", &self.additive_sum)
+            .field("after_additives", &self.after_additives)
+            .field("multiplicatives", &self.multiplicatives)
+            .field(
+                "suppressed_multiplicatives",
+                &self.suppressed_multiplicatives,
+            )
+            .field("after_multiplicatives", &self.after_multiplicatives)
+            .finish()
+    }
+}
+
+fn write_modifier<T>(f: &mut fmt::Formatter<'_>, sign: &str, modifier: &T) -> fmt::Result
+where
+    T: Modifier,
+    T::Raw: Display,
+    T::Metadata: Debug,
+{
+    write!(f, "    {sign} {}", modifier.raw())?;
+    match modifier.metadata() {
+        Some(metadata) => writeln!(f, " ({metadata:?})"),
+        None => writeln!(f),
+    }
+}
+
+fn write_modifiers<T>(
+    f: &mut fmt::Formatter<'_>,
+    sign: &str,
+    applied: &[T],
+    suppressed: &[T],
+) -> fmt::Result
+where
+    T: Modifier,
+    T::Raw: Display,
+    T::Metadata: Debug,
+{
+    for modifier in applied {
+        write_modifier(f, sign, modifier)?;
+    }
+    for modifier in suppressed {
+        write_modifier(f, &format!("[suppressed] {sign}"), modifier)?;
+    }
+    Ok(())
+}
+
+/// Writes `sum`, if a curve made it differ from the sum of `applied` modifiers.
+fn write_curved<T>(f: &mut fmt::Formatter<'_>, applied: &[T], sum: T::Raw) -> fmt::Result
+where
+    T: Modifier,
+    T::Raw: StatNumber + Display,
+{
+    let raw = applied.iter().fold(T::Raw::ZERO, |acc, m| acc + m.raw());
+    if sum != raw {
+        writeln!(f, "    ~ {sum} (curve)")?;
+    }
+    Ok(())
+}
+
+impl<Marker> Display for Breakdown<Marker>
+where
+    Marker: StatMarker,
+    Marker::Raw: Display,
+    Marker::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "base: {}", self.base)?;
+        for stage in &self.stages {
+            match stage.order {
+                isize::MIN => writeln!(f, "stage first:")?,
+                isize::MAX => writeln!(f, "stage last:")?,
+                order => writeln!(f, "stage {order}:")?,
+            }
+            if !stage.flats.is_empty() || !stage.suppressed_flats.is_empty() {
+                write_modifiers(f, "+", &stage.flats, &stage.suppressed_flats)?;
+                write_curved(f, &stage.flats, stage.flat_sum)?;
+                writeln!(f, "    = {}", stage.after_flats)?;
+            }
+            if !stage.additives.is_empty() || !stage.suppressed_additives.is_empty() {
+                write_modifiers(f, "+%", &stage.additives, &stage.suppressed_additives)?;
+                write_curved(f, &stage.additives, stage.additive_sum)?;
+                writeln!(f, "    = {}", stage.after_additives)?;
+            }
+            if !stage.multiplicatives.is_empty() || !stage.suppressed_multiplicatives.is_empty() {
+                write_modifiers(
+                    f,
+                    "x",
+                    &stage.multiplicatives,
+                    &stage.suppressed_multiplicatives,
+                )?;
+                writeln!(f, "    = {}", stage.after_multiplicatives)?;
+            }
+        }
+        if !self.floors.is_empty() {
+            writeln!(f, "floor:")?;
+            for floor in &self.floors {
+                write_modifier(f, ">=", floor)?;
+            }
+            writeln!(f, "    = {}", self.after_floors)?;
+        }
+        if !self.ceilings.is_empty() {
+            writeln!(f, "ceiling:")?;
+            for ceiling in &self.ceilings {
+                write_modifier(f, "<=", ceiling)?;
+            }
+            writeln!(f, "    = {}", self.after_ceilings)?;
+        }
+        if let Some(active) = &self.active_override {
+            writeln!(f, "override:")?;
+            write_modifier(f, "=", active)?;
+        }
+        if self.value != self.unconverted {
+            writeln!(f, "converted: {} -> {}", self.unconverted, self.value)?;
+        }
+        if self.unconverted != self.unclamped {
+            write!(f, "final: {} (clamped from {})", self.value, self.unclamped)
+        } else {
+            write!(f, "final: {}", self.value)
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::any::Any;
+
+use crate::{
+    modifier::ModifierId,
+    prelude::{
+        AdditiveAll, CeilingAll, FlatAll, FloorAll, MultiplicativeAll, OverrideAll, Stat,
+        StatMarker,
+    },
+};
+
+/// Type erased [`Stat`], used by containers of stats with different markers.
+pub(crate) trait ErasedStat {
+    fn cache_value(&mut self);
+
+    fn invalidate(&mut self);
+
+    /// Applies a [shared][crate::modifier::shared::All] modifier, if it is compatible with the
+    /// stat.
+    fn apply_shared(&mut self, modifier: &dyn Any) -> Option<ModifierId>;
+
+    fn remove_by_id(&mut self, id: ModifierId) -> bool;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<Marker, const N: usize> ErasedStat for Stat<Marker, N>
+where
+    Marker: StatMarker + 'static,
+{
+    fn cache_value(&mut self) {
+        Stat::cache_value(self);
+    }
+
+    fn invalidate(&mut self) {
+        self.cached = None;
+        self.context_cache = None;
+    }
+
+    fn apply_shared(&mut self, modifier: &dyn Any) -> Option<ModifierId> {
+        if let Some(&flat) = modifier.downcast_ref::<FlatAll<Marker::Raw, Marker::Metadata>>() {
+            Some(self.apply_flat_from_shared(flat))
+        } else if let Some(&additive) =
+            modifier.downcast_ref::<AdditiveAll<Marker::Raw, Marker::Metadata>>()
+        {
+            Some(self.apply_add_from_shared(additive))
+        } else if let Some(&multiplicative) =
+            modifier.downcast_ref::<MultiplicativeAll<Marker::Raw, Marker::Metadata>>()
+        {
+            Some(self.apply_mul_from_shared(multiplicative))
+        } else if let Some(&floor) =
+            modifier.downcast_ref::<FloorAll<Marker::Raw, Marker::Metadata>>()
+        {
+            Some(self.apply_floor_from_shared(floor))
+        } else if let Some(&ceiling) =
+            modifier.downcast_ref::<CeilingAll<Marker::Raw, Marker::Metadata>>()
+        {
+            Some(self.apply_ceiling_from_shared(ceiling))
+        } else {
+            modifier
+                .downcast_ref::<OverrideAll<Marker::Raw, Marker::Metadata>>()
+                .map(|&modifier| self.apply_override_from_shared(modifier))
+        }
+    }
+
+    fn remove_by_id(&mut self, id: ModifierId) -> bool {
+        let contains = self.contains(id);
+        Stat::remove_by_id(self, id);
+        contains
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    mem,
+    sync::atomic::{self, AtomicU64},
+};
+
+/// Opaque handle of an observer subscribed to a [`Stat`][crate::stat::Stat].
+///
+/// See [`Stat::subscribe`][crate::stat::Stat::subscribe].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+impl ObserverId {
+    fn next() -> Self {
+        Self(NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+
+/// Observer called with the old and the new final value of a stat, which can be shared between
+/// threads. Default observer type of [`Stat`][crate::stat::Stat].
+pub type Callback<R> = dyn FnMut(R, R) + Send + Sync;
+
+/// Observer called with the old and the new final value of a stat, which stays on its thread
+/// (e.g. capturing an `Rc`). Stats with local observers are neither [`Send`] nor [`Sync`].
+pub type LocalCallback<R> = dyn FnMut(R, R);
+
+/// Closures, which can be subscribed as observers of type `C`, i.e. [`Callback`] or
+/// [`LocalCallback`].
+pub trait IntoCallback<C: ?Sized> {
+    fn into_callback(self) -> Box<C>;
+}
+
+impl<R, F> IntoCallback<Callback<R>> for F
+where
+    F: FnMut(R, R) + Send + Sync + 'static,
+{
+    fn into_callback(self) -> Box<Callback<R>> {
+        Box::new(self)
+    }
+}
+
+impl<R, F> IntoCallback<LocalCallback<R>> for F
+where
+    F: FnMut(R, R) + 'static,
+{
+    fn into_callback(self) -> Box<LocalCallback<R>> {
+        Box::new(self)
+    }
+}
+
+/// Observers of a single stat together with the last final value reported to them.
+pub(crate) struct Observers<R, C: ?Sized> {
+    last: Option<R>,
+    callbacks: Vec<(ObserverId, Box<C>)>,
+    paused: bool,
+}
+
+impl<R: Copy + PartialEq, C: ?Sized + FnMut(R, R)> Observers<R, C> {
+    /// Whether there are observers to notify right now.
+    pub(crate) fn is_listening(&self) -> bool {
+        !self.paused && !self.callbacks.is_empty()
+    }
+
+    /// Stops notifying until [`Observers::resume`]. Returns whether they were already paused.
+    pub(crate) fn pause(&mut self) -> bool {
+        mem::replace(&mut self.paused, true)
+    }
+
+    pub(crate) fn resume(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub(crate) fn subscribe(&mut self, value: Option<R>, callback: Box<C>) -> ObserverId {
+        let id = ObserverId::next();
+        if self.callbacks.is_empty() {
+            self.last = value;
+        }
+        self.callbacks.push((id, callback));
+        id
+    }
+
+    pub(crate) fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        let len = self.callbacks.len();
+        self.callbacks.retain(|(observer, _)| *observer != id);
+        len != self.callbacks.len()
+    }
+
+    /// Calls every observer, if `value` differs from the last reported value.
+    pub(crate) fn notify(&mut self, value: Option<R>) {
+        let Some(new) = value else {
+            return;
+        };
+        if !self.is_listening() {
+            return;
+        }
+        match self.last.replace(new) {
+            Some(old) if old != new => {
+                for (_, callback) in &mut self.callbacks {
+                    callback(old, new);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<R, C: ?Sized> Observers<R, C> {
+    pub(crate) fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+}
+
+impl<R, C: ?Sized> Default for Observers<R, C> {
+    fn default() -> Self {
+        Self {
+            last: None,
+            callbacks: Vec::new(),
+            paused: false,
+        }
+    }
+}
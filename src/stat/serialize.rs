@@ -0,0 +1,134 @@
+use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    bound::Bound,
+    modifier::ModifierId,
+    prelude::{Additive, Ceiling, Flat, Floor, Multiplicative, Override, Stat, StatMarker},
+    stacking::Stacking,
+    timed::Duration,
+};
+
+/// Serialized form of a [`Stat`]. Cached value is left out.
+#[derive(Serialize)]
+#[serde(bound(serialize = "Marker::Raw: Serialize, Marker::Metadata: Serialize"))]
+struct StatRef<'a, Marker>
+where
+    Marker: StatMarker,
+{
+    base: Marker::Raw,
+    flats: &'a [Flat<Marker, Marker::Raw, Marker::Metadata>],
+    adds: &'a [Additive<Marker, Marker::Raw, Marker::Metadata>],
+    muls: &'a [Multiplicative<Marker, Marker::Raw, Marker::Metadata>],
+    floors: &'a [Floor<Marker, Marker::Raw, Marker::Metadata>],
+    ceilings: &'a [Ceiling<Marker, Marker::Raw, Marker::Metadata>],
+    overrides: &'a [Override<Marker, Marker::Raw, Marker::Metadata>],
+    timers: &'a [(ModifierId, Duration)],
+    stacking: &'a [(Marker::Metadata, Stacking)],
+    min: Option<Marker::Raw>,
+    max: Option<Marker::Raw>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "Marker::Raw: Deserialize<'de>, Marker::Metadata: Deserialize<'de>"))]
+struct StatData<Marker>
+where
+    Marker: StatMarker,
+{
+    base: Marker::Raw,
+    #[serde(default)]
+    flats: Vec<Flat<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    adds: Vec<Additive<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    muls: Vec<Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    floors: Vec<Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    ceilings: Vec<Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    overrides: Vec<Override<Marker, Marker::Raw, Marker::Metadata>>,
+    #[serde(default)]
+    timers: Vec<(ModifierId, Duration)>,
+    #[serde(default)]
+    stacking: Vec<(Marker::Metadata, Stacking)>,
+    #[serde(default)]
+    min: Option<Marker::Raw>,
+    #[serde(default)]
+    max: Option<Marker::Raw>,
+}
+
+fn constant<R: Copy, E: Error>(bound: &Option<Bound<R>>) -> Result<Option<R>, E> {
+    match bound {
+        Some(Bound::Value(value)) => Ok(Some(*value)),
+        Some(Bound::Dynamic(_)) => Err(E::custom("dynamic bounds can't be serialized")),
+        None => Ok(None),
+    }
+}
+
+impl<Marker, const N: usize, O> Serialize for Stat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized,
+    Marker::Raw: Serialize,
+    Marker::Metadata: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.flat_curve.is_some() || self.additive_curve.is_some() {
+            return Err(S::Error::custom("curves can't be serialized"));
+        }
+        if !self.conditions.is_empty() {
+            return Err(S::Error::custom(
+                "conditional modifiers can't be serialized",
+            ));
+        }
+        StatRef {
+            base: self.base,
+            flats: &self.flats,
+            adds: &self.adds,
+            muls: &self.muls,
+            floors: &self.floors,
+            ceilings: &self.ceilings,
+            overrides: &self.overrides,
+            timers: &self.timers,
+            stacking: &self.stacking,
+            min: constant(&self.min)?,
+            max: constant(&self.max)?,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Cached value gets recomputed after deserialization.
+impl<'de, Marker, const N: usize, O> Deserialize<'de> for Stat<Marker, N, O>
+where
+    Marker: StatMarker,
+    O: ?Sized + FnMut(Marker::Raw, Marker::Raw),
+    Marker::Raw: Deserialize<'de>,
+    Marker::Metadata: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = StatData::<Marker>::deserialize(deserializer)?;
+        let mut stat = Self {
+            base: data.base,
+            cached: None,
+            flats: data.flats.into_iter().collect(),
+            adds: data.adds.into_iter().collect(),
+            muls: data.muls.into_iter().collect(),
+            floors: data.floors.into_iter().collect(),
+            ceilings: data.ceilings.into_iter().collect(),
+            overrides: data.overrides.into_iter().collect(),
+            timers: data.timers.into_iter().collect(),
+            stacking: data.stacking.into_iter().collect(),
+            conditions: Default::default(),
+            context_cache: None,
+            min: data.min.map(Bound::Value),
+            max: data.max.map(Bound::Value),
+            flat_curve: None,
+            additive_curve: None,
+            conversion: None,
+            observers: Default::default(),
+        };
+        stat.cache_value();
+        Ok(stat)
+    }
+}
@@ -1,96 +1,910 @@
 use std::{
     fmt::Debug,
-    sync::{Mutex, MutexGuard},
+    ops::{Deref, DerefMut, Div, Sub},
+    sync::{
+        atomic::{self, AtomicBool, AtomicU64},
+        Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    thread,
 };
 
+use smallvec::SmallVec;
+
 use crate::{
-    modifier::shared::Shared,
-    prelude::{Additive, Flat, Multiplicative, Stat, StatMarker},
+    bound::Bound,
+    error::Error,
+    modifier::{shared::Shared, ModifierId},
+    number::StatNumber,
+    prelude::{Additive, Ceiling, Flat, Floor, Multiplicative, Override, Stat, StatMarker},
+    resource::Resource,
+    stacking::Stacking,
+    stat::{breakdown::Breakdown, observer::ObserverId},
+    timed::{Duration, Tick},
 };
 
-pub struct MiniStat<Marker, const N: usize = 2>(Mutex<Stat<Marker, N>>)
+/// [`Stat`] behind a [`RwLock`], shareable between threads.
+///
+/// Every change recomputes the final value and publishes it through an atomic before returning,
+/// so [`MiniStat::cached`] never blocks for [raw types][StatMarker::Raw], which
+/// [pack][crate::number::StatNumber::pack] into 64 bits. Other raw types read the value under a
+/// shared lock, and so do stats with [dynamic bounds][crate::bound::Bound::Dynamic], which have to
+/// be re-read on each call and thus disable publishing.
+///
+/// Observers get called by whichever thread changes the stat, so unlike with a bare [`Stat`], they
+/// are always [`Callback`][crate::stat::observer::Callback]s, i.e. [`Send`] and [`Sync`].
+pub struct MiniStat<Marker, const N: usize = 2>
 where
-    Marker: StatMarker;
+    Marker: StatMarker,
+{
+    stat: RwLock<Stat<Marker, N>>,
+    published: AtomicU64,
+    is_published: AtomicBool,
+    generation: AtomicU64,
+    writer: AtomicU64,
+}
+
+/// Exclusive access to the stat of a [`MiniStat`], which publishes the final value when dropped.
+pub struct StatMut<'a, Marker, const N: usize>
+where
+    Marker: StatMarker,
+{
+    stat: RwLockWriteGuard<'a, Stat<Marker, N>>,
+    published: &'a AtomicU64,
+    is_published: &'a AtomicBool,
+    generation: &'a AtomicU64,
+    writer: &'a AtomicU64,
+}
+
+impl<Marker, const N: usize> Deref for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    type Target = Stat<Marker, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stat
+    }
+}
+
+impl<Marker, const N: usize> DerefMut for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stat
+    }
+}
+
+impl<Marker, const N: usize> Drop for StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    fn drop(&mut self) {
+        // Computing the value may panic again, which would abort the unwinding thread; the lock
+        // gets poisoned instead and the previously published value stays.
+        if !thread::panicking() {
+            self.publish();
+        }
+        self.writer.store(0, atomic::Ordering::Release);
+    }
+}
+
+impl<Marker, const N: usize> StatMut<'_, Marker, N>
+where
+    Marker: StatMarker,
+{
+    fn publish(&mut self) {
+        let bits = self.stat.cache_value().cached().and_then(StatNumber::pack);
+        let Some(bits) = bits.filter(|_| !has_dynamic_bounds(&self.stat)) else {
+            self.is_published.store(false, atomic::Ordering::Release);
+            return;
+        };
+        // Only the writer stores, so nothing can change between the loads and the stores.
+        let unchanged = self.is_published.load(atomic::Ordering::Acquire)
+            && self.published.load(atomic::Ordering::Acquire) == bits;
+        if unchanged {
+            return;
+        }
+        self.published.store(bits, atomic::Ordering::Release);
+        self.is_published.store(true, atomic::Ordering::Release);
+        self.generation.fetch_add(1, atomic::Ordering::Release);
+    }
+}
 
 impl<Marker, const N: usize> MiniStat<Marker, N>
 where
     Marker: StatMarker,
-    Stat<Marker, N>: Default,
-    Flat<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Additive<Marker, Marker::Raw, Marker::Metadata>: Default,
-    Multiplicative<Marker, Marker::Raw, Marker::Metadata>: Default,
 {
     pub fn new(stat: Stat<Marker, N>) -> Self {
-        Self(Mutex::new(stat))
+        let this = Self {
+            stat: RwLock::new(stat),
+            published: AtomicU64::new(0),
+            is_published: AtomicBool::new(false),
+            generation: AtomicU64::new(0),
+            writer: AtomicU64::new(0),
+        };
+        drop(this.stat_mut());
+        this
     }
 
     pub fn with_base(base: Marker::Raw) -> Self {
-        Self(Mutex::new(Stat::<Marker, N>::with_base(base)))
+        Self::new(Stat::<Marker, N>::with_base(base))
     }
 
-    pub fn stat_mut(&self) -> MutexGuard<Stat<Marker, N>> {
-        self.0.lock().unwrap()
+    pub fn stat(&self) -> RwLockReadGuard<'_, Stat<Marker, N>> {
+        self.stat.read().unwrap()
+    }
+
+    pub fn stat_mut(&self) -> StatMut<'_, Marker, N> {
+        self.guard(self.stat.write().unwrap())
+    }
+
+    fn guard<'a>(&'a self, stat: RwLockWriteGuard<'a, Stat<Marker, N>>) -> StatMut<'a, Marker, N> {
+        self.writer.store(thread_token(), atomic::Ordering::Release);
+        StatMut {
+            stat,
+            published: &self.published,
+            is_published: &self.is_published,
+            generation: &self.generation,
+            writer: &self.writer,
+        }
+    }
+
+    /// Whether the stat is locked by a [`StatMut`] of the current thread, e.g. because an observer
+    /// is being called.
+    fn is_writing(&self) -> bool {
+        self.writer.load(atomic::Ordering::Acquire) == thread_token()
+    }
+
+    /// Number of times a different final value was published, i.e. a cheap way to tell, whether
+    /// it changed since the last read. Releasing a [`StatMut`] without changing the final value,
+    /// e.g. after [`MiniStat::evaluate_with`] or a rolled back [batch][MiniStat::batch], doesn't
+    /// count. Stays put for raw types, which don't
+    /// [pack][crate::number::StatNumber::pack], and while the stat has
+    /// [dynamic bounds][crate::bound::Bound::Dynamic].
+    pub fn generation(&self) -> u64 {
+        self.generation.load(atomic::Ordering::Acquire)
+    }
+
+    /// Last published final value, if publishing isn't disabled.
+    fn published(&self) -> Option<Marker::Raw> {
+        if !self.is_published.load(atomic::Ordering::Acquire) {
+            return None;
+        }
+        Marker::Raw::unpack(self.published.load(atomic::Ordering::Acquire))
     }
 
     pub fn base(&self) -> Marker::Raw {
-        self.0.lock().unwrap().base
+        self.stat().base
+    }
+
+    pub fn set_base(&self, base: Marker::Raw) {
+        self.stat_mut().set_base(base);
     }
 
+    pub fn add_to_base(&self, amount: Marker::Raw) {
+        self.stat_mut().add_to_base(amount);
+    }
+
+    pub fn map_base(&self, f: impl FnOnce(Marker::Raw) -> Marker::Raw) {
+        self.stat_mut().map_base(f);
+    }
+
+    /// Final value of the stat, read without locking if the raw type
+    /// [packs][crate::number::StatNumber::pack] into 64 bits.
     pub fn cached(&self) -> Marker::Raw {
-        self.0.lock().unwrap().cache_value();
-        self.0.lock().unwrap().cached().unwrap()
+        if let Some(value) = self.published() {
+            return value;
+        }
+        if let Some(value) = self.stat().cached() {
+            return value;
+        }
+        let mut stat = self.stat_mut();
+        stat.cache_value();
+        stat.cached()
+            .expect("value is cached while the stat is locked")
     }
 
-    pub fn apply_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.lock().unwrap().apply_flat(flat);
+    pub fn breakdown(&self) -> Breakdown<Marker> {
+        self.stat().breakdown()
     }
 
-    pub fn apply_flat_from_shared<T>(&self, flat: T)
+    pub fn apply_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.stat_mut().apply_flat(flat)
+    }
+
+    pub fn apply_flat_from_shared<T>(&self, flat: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Flat<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.lock().unwrap().apply_flat_from_shared(flat);
+        self.stat_mut().apply_flat_from_shared(flat)
     }
 
-    pub fn apply_add(&self, add: Additive<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.lock().unwrap().apply_add(add);
+    pub fn apply_add(&self, add: Additive<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.stat_mut().apply_add(add)
     }
 
-    pub fn apply_add_from_shared<T>(&self, add: T)
+    pub fn apply_add_from_shared<T>(&self, add: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Additive<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.lock().unwrap().apply_add_from_shared(add);
+        self.stat_mut().apply_add_from_shared(add)
     }
 
-    pub fn apply_mul(&self, mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.lock().unwrap().apply_mul(mul);
+    pub fn apply_mul(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().apply_mul(mul)
     }
 
-    pub fn apply_mul_from_shared<T>(&self, mul: T)
+    pub fn apply_mul_from_shared<T>(&self, mul: T) -> ModifierId
     where
         T: Shared<Marker, TargetModifier = Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
     {
-        self.0.lock().unwrap().apply_mul_from_shared(mul);
+        self.stat_mut().apply_mul_from_shared(mul)
+    }
+
+    pub fn apply_floor(&self, floor: Floor<Marker, Marker::Raw, Marker::Metadata>) -> ModifierId {
+        self.stat_mut().apply_floor(floor)
+    }
+
+    pub fn apply_floor_from_shared<T>(&self, floor: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.stat_mut().apply_floor_from_shared(floor)
+    }
+
+    pub fn apply_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().apply_ceiling(ceiling)
+    }
+
+    pub fn apply_ceiling_from_shared<T>(&self, ceiling: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.stat_mut().apply_ceiling_from_shared(ceiling)
+    }
+
+    pub fn apply_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> ModifierId {
+        self.stat_mut().apply_override(modifier)
+    }
+
+    pub fn apply_override_from_shared<T>(&self, modifier: T) -> ModifierId
+    where
+        T: Shared<Marker, TargetModifier = Override<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        self.stat_mut().apply_override_from_shared(modifier)
+    }
+
+    pub fn apply_flat_timed(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_flat_timed(flat, duration)
+    }
+
+    pub fn apply_add_timed(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_add_timed(add, duration)
+    }
+
+    pub fn apply_mul_timed(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_mul_timed(mul, duration)
+    }
+
+    pub fn apply_floor_timed(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_floor_timed(floor, duration)
+    }
+
+    pub fn apply_ceiling_timed(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_ceiling_timed(ceiling, duration)
+    }
+
+    pub fn apply_override_timed(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> ModifierId {
+        self.stat_mut().apply_override_timed(modifier, duration)
+    }
+
+    pub fn apply_flat_if<C: 'static>(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_flat_if(flat, condition)
+    }
+
+    pub fn apply_add_if<C: 'static>(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_add_if(add, condition)
+    }
+
+    pub fn apply_mul_if<C: 'static>(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_mul_if(mul, condition)
+    }
+
+    pub fn apply_floor_if<C: 'static>(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_floor_if(floor, condition)
+    }
+
+    pub fn apply_ceiling_if<C: 'static>(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_ceiling_if(ceiling, condition)
+    }
+
+    pub fn apply_override_if<C: 'static>(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> ModifierId {
+        self.stat_mut().apply_override_if(modifier, condition)
+    }
+
+    pub fn evaluate_with<C: 'static>(&self, context: &C) -> Marker::Raw {
+        self.stat_mut().evaluate_with(context)
+    }
+
+    pub fn is_stale<C: 'static>(&self, context: &C) -> bool {
+        self.stat().is_stale(context)
+    }
+
+    pub fn tick(&self, delta: Tick) -> SmallVec<[ModifierId; N]> {
+        self.stat_mut().tick(delta)
+    }
+
+    pub fn remaining(&self, id: ModifierId) -> Option<Duration> {
+        self.stat().remaining(id)
     }
 
     pub fn remove_flat(&self, flat: Flat<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.lock().unwrap().remove_flat(flat);
+        self.stat_mut().remove_flat(flat);
     }
 
     pub fn remove_add(&self, additive: Additive<Marker, Marker::Raw, Marker::Metadata>) {
-        self.0.lock().unwrap().remove_add(additive);
+        self.stat_mut().remove_add(additive);
     }
 
     pub fn remove_mul(
         &self,
         multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
     ) {
-        self.0.lock().unwrap().remove_mul(multiplicative);
+        self.stat_mut().remove_mul(multiplicative);
+    }
+
+    pub fn remove_floor(&self, floor: Floor<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().remove_floor(floor);
+    }
+
+    pub fn remove_ceiling(&self, ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().remove_ceiling(ceiling);
+    }
+
+    pub fn remove_override(&self, modifier: Override<Marker, Marker::Raw, Marker::Metadata>) {
+        self.stat_mut().remove_override(modifier);
+    }
+
+    pub fn remove_by_id(&self, id: ModifierId) {
+        self.stat_mut().remove_by_id(id);
+    }
+
+    pub fn remove_with_metadata(&self, metadata: Marker::Metadata) {
+        self.stat_mut().remove_with_metadata(metadata);
+    }
+
+    pub fn set_stacking(&self, metadata: Marker::Metadata, stacking: Stacking) {
+        self.stat_mut().set_stacking(metadata, stacking);
+    }
+
+    /// See [`Stat::subscribe`]. Observers are called while the stat is locked, so they may access
+    /// this `MiniStat` only through its `try_` methods, which return [`Error::BorrowConflict`].
+    /// They are called from the thread changing the stat, so they must be [`Send`] and [`Sync`].
+    pub fn subscribe(
+        &self,
+        observer: impl FnMut(Marker::Raw, Marker::Raw) + Send + Sync + 'static,
+    ) -> ObserverId {
+        self.stat_mut().subscribe(observer)
+    }
+
+    pub fn unsubscribe(&self, id: ObserverId) {
+        self.stat_mut().unsubscribe(id);
+    }
+
+    /// See [`Stat::batch`]. The stat stays locked for the whole batch and its final value gets
+    /// published once.
+    pub fn batch<T, E>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, N>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        self.stat_mut().batch(f)
     }
 }
 
+/// Fallible variants, returning [`Error::Poisoned`] instead of panicking when a thread panicked
+/// while modifying the stat, and [`Error::BorrowConflict`] instead of deadlocking when the current
+/// thread holds a [`StatMut`], e.g. in an observer.
+///
+/// Shared guards from [`MiniStat::stat`] aren't tracked, so acquiring a `StatMut` while holding
+/// one on the same thread still deadlocks.
+impl<Marker, const N: usize> MiniStat<Marker, N>
+where
+    Marker: StatMarker,
+{
+    pub fn try_stat(&self) -> Result<RwLockReadGuard<'_, Stat<Marker, N>>, Error> {
+        if self.is_writing() {
+            return Err(Error::BorrowConflict);
+        }
+        Ok(self.stat.read()?)
+    }
+
+    pub fn try_stat_mut(&self) -> Result<StatMut<'_, Marker, N>, Error> {
+        if self.is_writing() {
+            return Err(Error::BorrowConflict);
+        }
+        Ok(self.guard(self.stat.write()?))
+    }
+
+    /// Final value of the stat. The published value is returned even if the stat is poisoned.
+    ///
+    /// While the current thread holds a [`StatMut`], the published value may be outdated, so
+    /// [`Error::BorrowConflict`] is returned instead; observers get the new value as an argument.
+    pub fn try_cached(&self) -> Result<Marker::Raw, Error> {
+        if self.is_writing() {
+            return Err(Error::BorrowConflict);
+        }
+        if let Some(value) = self.published() {
+            return Ok(value);
+        }
+        if let Some(value) = self.try_stat()?.cached() {
+            return Ok(value);
+        }
+        self.try_stat_mut()?
+            .cache_value()
+            .cached()
+            .ok_or(Error::CacheMissing)
+    }
+
+    pub fn try_set_base(&self, base: Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.set_base(base);
+        Ok(())
+    }
+
+    pub fn try_add_to_base(&self, amount: Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.add_to_base(amount);
+        Ok(())
+    }
+
+    pub fn try_map_base(&self, f: impl FnOnce(Marker::Raw) -> Marker::Raw) -> Result<(), Error> {
+        self.try_stat_mut()?.map_base(f);
+        Ok(())
+    }
+
+    pub fn try_apply_flat(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat(flat))
+    }
+
+    pub fn try_apply_flat_from_shared<T>(&self, flat: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Flat<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_flat_from_shared(flat))
+    }
+
+    pub fn try_apply_add(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add(add))
+    }
+
+    pub fn try_apply_add_from_shared<T>(&self, add: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Additive<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_add_from_shared(add))
+    }
+
+    pub fn try_apply_mul(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul(mul))
+    }
+
+    pub fn try_apply_mul_from_shared<T>(&self, mul: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Multiplicative<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_mul_from_shared(mul))
+    }
+
+    pub fn try_apply_floor(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor(floor))
+    }
+
+    pub fn try_apply_floor_from_shared<T>(&self, floor: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Floor<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_floor_from_shared(floor))
+    }
+
+    pub fn try_apply_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling(ceiling))
+    }
+
+    pub fn try_apply_ceiling_from_shared<T>(&self, ceiling: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Ceiling<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_ceiling_from_shared(ceiling))
+    }
+
+    pub fn try_apply_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_override(modifier))
+    }
+
+    pub fn try_apply_override_from_shared<T>(&self, modifier: T) -> Result<ModifierId, Error>
+    where
+        T: Shared<Marker, TargetModifier = Override<Marker, Marker::Raw, Marker::Metadata>>,
+    {
+        Ok(self.try_stat_mut()?.apply_override_from_shared(modifier))
+    }
+
+    pub fn try_apply_flat_timed(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat_timed(flat, duration))
+    }
+
+    pub fn try_apply_add_timed(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add_timed(add, duration))
+    }
+
+    pub fn try_apply_mul_timed(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul_timed(mul, duration))
+    }
+
+    pub fn try_apply_floor_timed(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor_timed(floor, duration))
+    }
+
+    pub fn try_apply_ceiling_timed(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling_timed(ceiling, duration))
+    }
+
+    pub fn try_apply_override_timed(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        duration: Duration,
+    ) -> Result<ModifierId, Error> {
+        Ok(self
+            .try_stat_mut()?
+            .apply_override_timed(modifier, duration))
+    }
+
+    pub fn try_apply_flat_if<C: 'static>(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_flat_if(flat, condition))
+    }
+
+    pub fn try_apply_add_if<C: 'static>(
+        &self,
+        add: Additive<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_add_if(add, condition))
+    }
+
+    pub fn try_apply_mul_if<C: 'static>(
+        &self,
+        mul: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_mul_if(mul, condition))
+    }
+
+    pub fn try_apply_floor_if<C: 'static>(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_floor_if(floor, condition))
+    }
+
+    pub fn try_apply_ceiling_if<C: 'static>(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_ceiling_if(ceiling, condition))
+    }
+
+    pub fn try_apply_override_if<C: 'static>(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+        condition: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Result<ModifierId, Error> {
+        Ok(self.try_stat_mut()?.apply_override_if(modifier, condition))
+    }
+
+    pub fn try_evaluate_with<C: 'static>(&self, context: &C) -> Result<Marker::Raw, Error> {
+        Ok(self.try_stat_mut()?.evaluate_with(context))
+    }
+
+    pub fn try_tick(&self, delta: Tick) -> Result<SmallVec<[ModifierId; N]>, Error> {
+        Ok(self.try_stat_mut()?.tick(delta))
+    }
+
+    pub fn try_remove_flat(
+        &self,
+        flat: Flat<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_flat(flat);
+        Ok(())
+    }
+
+    pub fn try_remove_add(
+        &self,
+        additive: Additive<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_add(additive);
+        Ok(())
+    }
+
+    pub fn try_remove_mul(
+        &self,
+        multiplicative: Multiplicative<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_mul(multiplicative);
+        Ok(())
+    }
+
+    pub fn try_remove_floor(
+        &self,
+        floor: Floor<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_floor(floor);
+        Ok(())
+    }
+
+    pub fn try_remove_ceiling(
+        &self,
+        ceiling: Ceiling<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_ceiling(ceiling);
+        Ok(())
+    }
+
+    pub fn try_remove_override(
+        &self,
+        modifier: Override<Marker, Marker::Raw, Marker::Metadata>,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_override(modifier);
+        Ok(())
+    }
+
+    pub fn try_remove_by_id(&self, id: ModifierId) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_by_id(id);
+        Ok(())
+    }
+
+    pub fn try_remove_with_metadata(&self, metadata: Marker::Metadata) -> Result<(), Error> {
+        self.try_stat_mut()?.remove_with_metadata(metadata);
+        Ok(())
+    }
+
+    pub fn try_set_stacking(
+        &self,
+        metadata: Marker::Metadata,
+        stacking: Stacking,
+    ) -> Result<(), Error> {
+        self.try_stat_mut()?.set_stacking(metadata, stacking);
+        Ok(())
+    }
+
+    pub fn try_subscribe(
+        &self,
+        observer: impl FnMut(Marker::Raw, Marker::Raw) + Send + Sync + 'static,
+    ) -> Result<ObserverId, Error> {
+        Ok(self.try_stat_mut()?.subscribe(observer))
+    }
+
+    pub fn try_unsubscribe(&self, id: ObserverId) -> Result<(), Error> {
+        self.try_stat_mut()?.unsubscribe(id);
+        Ok(())
+    }
+
+    /// See [`MiniStat::batch`]. The outer error is about accessing the stat, the inner one is
+    /// returned by `f`.
+    pub fn try_batch<T, E>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, N>) -> Result<T, E>,
+    ) -> Result<Result<T, E>, Error> {
+        Ok(self.try_stat_mut()?.batch(f))
+    }
+
+    /// Recovers from a panic while modifying the stat, keeping the stat as the panicking thread
+    /// left it and publishing its final value again.
+    pub fn clear_poison(&self) {
+        self.stat.clear_poison();
+        drop(self.stat_mut());
+    }
+}
+
+/// Whether a [bound][Stat::set_min] of `stat` is [read on each query][Bound::Dynamic].
+fn has_dynamic_bounds<Marker: StatMarker, const N: usize>(stat: &Stat<Marker, N>) -> bool {
+    [&stat.min, &stat.max]
+        .into_iter()
+        .any(|bound| matches!(bound, Some(Bound::Dynamic(_))))
+}
+
+/// Nonzero token identifying the current thread, as [`std::thread::ThreadId`] can't be stored in an
+/// atomic.
+fn thread_token() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+
+    thread_local! {
+        static TOKEN: u64 = NEXT.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+
+    TOKEN.with(|token| *token)
+}
+
 impl<Marker, const N: usize> Debug for MiniStat<Marker, N>
+where
+    Marker: StatMarker + Debug,
+    <Marker as StatMarker>::Raw: Debug,
+    <Marker as StatMarker>::Metadata: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.stat().fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Marker, const N: usize> serde::Serialize for MiniStat<Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: serde::Serialize,
+    Marker::Metadata: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.stat().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Marker, const N: usize> serde::Deserialize<'de> for MiniStat<Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: serde::Deserialize<'de>,
+    Marker::Metadata: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Stat::deserialize(deserializer).map(Self::new)
+    }
+}
+
+pub struct MiniResource<Marker, const N: usize = 2>(Mutex<Resource<Marker, N>>)
+where
+    Marker: StatMarker;
+
+impl<Marker, const N: usize> MiniResource<Marker, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    pub fn new(resource: Resource<Marker, N>) -> Self {
+        Self(Mutex::new(resource))
+    }
+
+    pub fn with_max(base: Marker::Raw) -> Self {
+        Self(Mutex::new(Resource::<Marker, N>::with_max(base)))
+    }
+
+    pub fn resource_mut(&self) -> MutexGuard<'_, Resource<Marker, N>> {
+        self.0.lock().unwrap()
+    }
+
+    pub fn current(&self) -> Marker::Raw {
+        self.0.lock().unwrap().current()
+    }
+
+    pub fn max(&self) -> Marker::Raw {
+        self.0.lock().unwrap().max()
+    }
+
+    pub fn damage(&self, amount: Marker::Raw) -> Marker::Raw {
+        self.0.lock().unwrap().damage(amount)
+    }
+
+    pub fn heal(&self, amount: Marker::Raw) -> Marker::Raw {
+        self.0.lock().unwrap().heal(amount)
+    }
+
+    pub fn spend(&self, amount: Marker::Raw) -> bool {
+        self.0.lock().unwrap().spend(amount)
+    }
+
+    pub fn regen(&self, dt: Marker::Raw) -> Marker::Raw {
+        self.0.lock().unwrap().regen(dt)
+    }
+}
+
+impl<Marker, const N: usize> Debug for MiniResource<Marker, N>
 where
     Marker: StatMarker + Debug,
     <Marker as StatMarker>::Raw: Debug,
@@ -0,0 +1,40 @@
+/// Remaining lifetime of a timed modifier.
+///
+/// See [`Stat::tick`][crate::stat::Stat::tick].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Duration {
+    /// Modifier expires after given amount of seconds.
+    Seconds(f32),
+    /// Modifier expires after given amount of turns.
+    Turns(u32),
+}
+
+/// Time passed since the last [tick][crate::stat::Stat::tick].
+///
+/// Only advances durations of the same kind, e.g. [`Tick::Turns`] leaves
+/// [`Duration::Seconds`] untouched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tick {
+    /// Seconds passed.
+    Seconds(f32),
+    /// Turns passed.
+    Turns(u32),
+}
+
+impl Duration {
+    /// Advances the duration by `delta`, returning `true` if it has expired.
+    pub fn advance(&mut self, delta: Tick) -> bool {
+        match (self, delta) {
+            (Self::Seconds(left), Tick::Seconds(dt)) => {
+                *left -= dt;
+                *left <= 0.
+            }
+            (Self::Turns(left), Tick::Turns(turns)) => {
+                *left = left.saturating_sub(turns);
+                *left == 0
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,4 +1,20 @@
-use mini_stat::{modifier::shared::Shared, prelude::*};
+use std::{
+    cell::RefCell,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    sync::{mpsc, Arc, Mutex},
+};
+
+use mini_stat::{
+    bound::Bound,
+    curve::Curve,
+    formula::{AdditivePercent, FlatsLast, IncreasedMore},
+    modifier::shared::Shared,
+    prelude::*,
+    stat::observer::LocalCallback,
+    timed::{Duration, Tick},
+};
 
 #[derive(Debug, Default)]
 struct Dummy;
@@ -7,6 +23,8 @@ impl StatMarker for Dummy {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 #[test]
@@ -14,10 +32,10 @@ fn specs() {
     let mut stat = Stat::<Dummy>::with_base(3.);
     println!("{:.0}", stat.cached().unwrap());
 
-    stat.apply_flat(Flat::from_raw(1.))
-        .apply_flat(Flat::from_raw(2.))
-        .apply_flat(Flat::from_raw(3.))
-        .cache_value();
+    stat.apply_flat(Flat::from_raw(1.));
+    stat.apply_flat(Flat::from_raw(2.));
+    stat.apply_flat(Flat::from_raw(3.));
+    stat.cache_value();
     println!("{:.0}", stat.cached().unwrap());
     println!("{stat:#?}");
 
@@ -26,10 +44,10 @@ fn specs() {
     let mut stat = Stat::<Dummy>::with_base(6.);
     println!("{:.0}", stat.cached().unwrap());
 
-    stat.apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.3))
-        .cache_value();
+    stat.apply_add(Additive::from_raw(0.1));
+    stat.apply_add(Additive::from_raw(0.1));
+    stat.apply_add(Additive::from_raw(0.3));
+    stat.cache_value();
     println!("{:.0}", stat.cached().unwrap());
     println!("{stat:#?}");
 
@@ -38,10 +56,10 @@ fn specs() {
     let mut stat = Stat::<Dummy>::with_base(1.);
     println!("{:.0}", stat.cached().unwrap());
 
-    stat.apply_mul(Multiplicative::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(3.))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    stat.apply_mul(Multiplicative::from_raw(0.5));
+    stat.apply_mul(Multiplicative::from_raw(3.));
+    stat.apply_mul(Multiplicative::from_raw(6.));
+    stat.cache_value();
     println!("{:.0}", stat.cached().unwrap());
     println!("{stat:#?}");
 
@@ -50,10 +68,10 @@ fn specs() {
     let mut stat = Stat::<Dummy>::with_base(0.);
     println!("{:.0}", stat.cached().unwrap());
 
-    stat.apply_flat(Flat::from_raw(1.))
-        .apply_add(Additive::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    stat.apply_flat(Flat::from_raw(1.));
+    stat.apply_add(Additive::from_raw(0.5));
+    stat.apply_mul(Multiplicative::from_raw(6.));
+    stat.cache_value();
     println!("{:.0}", stat.cached().unwrap());
     println!("{stat:#?}");
 
@@ -76,6 +94,8 @@ impl StatMarker for A {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -85,6 +105,8 @@ impl StatMarker for B {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 #[test]
@@ -118,6 +140,8 @@ impl StatMarker for SomeGroup {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 impl Shared<A> for Flat<SomeGroup, f64, &'static str> {
@@ -159,3 +183,603 @@ fn custom_group() {
 
     assert_eq!(b.cache_value().cached(), Some(7.));
 }
+
+#[test]
+fn ordered_stages() {
+    let mut stat = Stat::<Dummy>::with_base(2.);
+
+    stat.apply_flat(Flat::from_raw(1.));
+    stat.apply_mul(Multiplicative::from_raw(3.).at::<First>());
+    stat.cache_value();
+
+    assert_eq!(stat.cached(), Some(7.));
+
+    stat.apply_flat(Flat::from_raw(1.).at::<Last>());
+    stat.apply_mul(Multiplicative::from_raw(2.));
+    stat.cache_value();
+
+    assert_eq!(stat.cached(), Some(15.));
+
+    stat.remove_mul(Multiplicative::from_raw(3.)).cache_value();
+
+    assert_eq!(stat.cached(), Some(15.));
+
+    stat.remove_mul(Multiplicative::from_raw(3.).at::<First>())
+        .cache_value();
+
+    assert_eq!(stat.cached(), Some(7.));
+}
+
+#[test]
+fn metadata() {
+    let mut stat = Stat::<Dummy>::with_base(1.);
+
+    stat.apply_flat(Flat::with_metadata(2., "sword"));
+    stat.apply_flat(Flat::with_metadata(3., "ring"));
+    stat.apply_add(Additive::with_metadata(1., "sword"));
+    stat.apply_mul(Multiplicative::from_raw(2.));
+    stat.cache_value();
+
+    assert_eq!(stat.cached(), Some(24.));
+    assert_eq!(stat.flats_with_metadata("sword").count(), 1);
+    assert_eq!(stat.additives_with_metadata("sword").count(), 1);
+    assert_eq!(stat.multiplicatives_with_metadata("sword").count(), 0);
+    assert_eq!(
+        stat.flats_with_metadata("ring")
+            .map(|m| m.raw())
+            .sum::<f64>(),
+        3.
+    );
+
+    stat.remove_with_metadata("sword").cache_value();
+
+    assert_eq!(stat.cached(), Some(8.));
+    assert_eq!(stat.flats_with_metadata("sword").count(), 0);
+    assert_eq!(stat.flats().len(), 1);
+    assert_eq!(stat.multiplicatives().len(), 1);
+}
+
+#[test]
+fn remove_by_id() {
+    let mut stat = Stat::<Dummy>::with_base(1.);
+
+    let first = stat.apply_flat(Flat::from_raw(5.));
+    let second = stat.apply_flat(Flat::from_raw(5.));
+    let mul = stat.apply_mul(Multiplicative::from_raw(2.));
+
+    assert_ne!(first, second);
+    assert_eq!(stat.cache_value().cached(), Some(22.));
+
+    stat.remove_by_id(second).cache_value();
+
+    assert_eq!(stat.cached(), Some(12.));
+    assert_eq!(stat.flats().len(), 1);
+    assert_eq!(stat.flats()[0].id(), Some(first));
+
+    stat.remove_by_id(second).remove_by_id(mul).cache_value();
+
+    assert_eq!(stat.cached(), Some(6.));
+}
+
+#[test]
+fn timed() {
+    let mut stat = Stat::<Dummy>::with_base(1.);
+
+    let buff = stat.apply_flat_timed(Flat::from_raw(2.), Duration::Seconds(1.5));
+    let debuff = stat.apply_mul_timed(Multiplicative::from_raw(0.5), Duration::Turns(2));
+    stat.apply_add(Additive::from_raw(1.));
+
+    assert_eq!(stat.cache_value().cached(), Some(3.));
+
+    assert!(stat.tick(Tick::Seconds(1.)).is_empty());
+    assert!(stat.tick(Tick::Turns(1)).is_empty());
+    assert_eq!(stat.remaining(buff), Some(Duration::Seconds(0.5)));
+    assert_eq!(stat.remaining(debuff), Some(Duration::Turns(1)));
+
+    assert_eq!(stat.tick(Tick::Seconds(1.)).as_slice(), &[buff]);
+    assert_eq!(stat.cache_value().cached(), Some(1.));
+
+    stat.remove_by_id(debuff);
+
+    assert_eq!(stat.remaining(debuff), None);
+    assert!(stat.tick(Tick::Turns(1)).is_empty());
+    assert_eq!(stat.cache_value().cached(), Some(2.));
+
+    let ring = stat.apply_flat_timed(Flat::with_metadata(1., "ring"), Duration::Turns(3));
+    let amulet = stat.apply_floor_timed(Floor::from_raw(0.), Duration::Turns(3));
+    let floor = *stat.floors().first().unwrap();
+    stat.remove_with_metadata("ring");
+    stat.remove_floor(floor);
+
+    assert_eq!(stat.remaining(ring), None);
+    assert_eq!(stat.remaining(amulet), None);
+}
+
+#[test]
+fn bounds() {
+    let mut stat = Stat::<Dummy>::with_base(1.);
+    stat.set_min(0.).set_max(Bound::from_fn(|| 10.));
+
+    stat.apply_flat(Flat::from_raw(-3.));
+    stat.cache_value();
+
+    assert_eq!(stat.cached(), Some(0.));
+    assert_eq!(stat.unclamped(), Some(-2.));
+    assert!(stat.is_clamped());
+
+    stat.apply_flat(Flat::from_raw(20.));
+    stat.cache_value();
+
+    assert_eq!(stat.cached(), Some(10.));
+    assert_eq!(stat.unclamped(), Some(18.));
+    assert!(stat.is_clamped());
+
+    stat.clear_bounds();
+
+    assert_eq!(stat.cached(), Some(18.));
+    assert!(!stat.is_clamped());
+}
+
+#[test]
+fn dynamic_bounds() {
+    let (sender, receiver) = mpsc::channel();
+    let cap = Arc::new(Mutex::new(50.));
+    let mut stat = Stat::<Dummy>::with_base(40.);
+    stat.set_max(Bound::from_fn({
+        let cap = cap.clone();
+        move || *cap.lock().unwrap()
+    }));
+    stat.cache_value();
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    *cap.lock().unwrap() = 20.;
+
+    assert_eq!(stat.cached(), Some(20.));
+    assert_eq!(receiver.try_iter().count(), 0);
+
+    stat.cache_value();
+
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(40., 20.)]);
+}
+
+#[test]
+fn breakdown() {
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.set_max(40.);
+
+    stat.apply_flat(Flat::with_metadata(5., "sword"));
+    stat.apply_add(Additive::from_raw(0.5));
+    stat.apply_mul(Multiplicative::with_metadata(2., "rage").at::<Last>());
+    stat.apply_flat(Flat::from_raw(2.).at::<First>());
+
+    let breakdown = stat.breakdown();
+
+    assert_eq!(breakdown.base, 10.);
+    assert_eq!(breakdown.stages.len(), 3);
+    assert_eq!(breakdown.stages[0].after_multiplicatives, 12.);
+    assert_eq!(breakdown.stages[1].input, 12.);
+    assert_eq!(breakdown.stages[1].flats[0].metadata(), Some("sword"));
+    assert_eq!(breakdown.stages[1].after_flats, 17.);
+    assert_eq!(breakdown.stages[1].additive_sum, 0.5);
+    assert_eq!(breakdown.stages[1].after_additives, 25.5);
+    assert_eq!(breakdown.stages[2].after_multiplicatives, 51.);
+    assert_eq!(breakdown.unclamped, 51.);
+    assert_eq!(breakdown.value, 40.);
+
+    stat.cache_value();
+
+    assert_eq!(stat.unclamped(), Some(breakdown.unclamped));
+    assert_eq!(stat.cached(), Some(breakdown.value));
+    assert_eq!(
+        breakdown.to_string(),
+        "base: 10
+stage first:
+    + 2
+    = 12
+stage 0:
+    + 5 (\"sword\")
+    = 17
+    +% 0.5
+    = 25.5
+stage last:
+    x 2 (\"rage\")
+    = 51
+final: 40 (clamped from 51)"
+    );
+}
+
+#[test]
+fn observers() {
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let mut stat = Stat::<Dummy>::with_base(10.);
+
+    let observer = stat.subscribe({
+        let changes = changes.clone();
+        move |old, new| changes.lock().unwrap().push((old, new))
+    });
+
+    let buff = stat.apply_flat(Flat::from_raw(5.));
+    stat.apply_mul(Multiplicative::from_raw(1.));
+    stat.set_max(12.);
+    stat.remove_by_id(buff);
+
+    assert_eq!(
+        *changes.lock().unwrap(),
+        [(10., 15.), (15., 12.), (12., 10.)]
+    );
+
+    stat.unsubscribe(observer);
+    stat.apply_flat(Flat::from_raw(1.));
+
+    assert_eq!(changes.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn local_observers() {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let mut stat = Stat::<Dummy, 2, LocalCallback<f64>>::with_base(10.);
+
+    stat.subscribe({
+        let changes = changes.clone();
+        move |old, new| changes.borrow_mut().push((old, new))
+    });
+    stat.apply_flat(Flat::from_raw(5.));
+
+    assert_eq!(*changes.borrow(), [(10., 15.)]);
+}
+
+#[test]
+fn base() {
+    let (sender, receiver) = mpsc::channel();
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.apply_mul(Multiplicative::from_raw(2.));
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    stat.set_base(20.);
+    assert_eq!(stat.cached(), Some(40.));
+
+    stat.add_to_base(5.).map_base(|base| base * 2.);
+    assert_eq!(stat.base(), 50.);
+    assert_eq!(stat.cached(), Some(100.));
+
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        [(20., 40.), (40., 50.), (50., 100.)]
+    );
+}
+
+#[test]
+fn overrides() {
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.set_min(1.);
+    stat.apply_flat(Flat::from_raw(5.));
+
+    let petrify = stat.apply_override(Override::with_metadata(0., "petrify"));
+    let cap = stat.apply_override(Override::with_metadata(75., "cap").at::<Last>());
+    stat.apply_override_from_shared(OverrideAll::<f64, &str>::with_metadata(50., "aura"));
+
+    let breakdown = stat.breakdown();
+
+    assert_eq!(breakdown.after_stages, 15.);
+    assert_eq!(breakdown.overrides.len(), 3);
+    assert_eq!(breakdown.active_override.unwrap().id(), Some(cap));
+    assert_eq!(stat.cache_value().cached(), Some(75.));
+
+    stat.remove_by_id(cap);
+    assert_eq!(stat.cache_value().cached(), Some(50.));
+
+    stat.remove_with_metadata("aura");
+    assert_eq!(stat.cache_value().unclamped(), Some(0.));
+    assert_eq!(stat.cached(), Some(1.));
+    assert_eq!(
+        stat.breakdown().to_string(),
+        "base: 10
+stage 0:
+    + 5
+    = 15
+override:
+    = 0 (\"petrify\")
+final: 1 (clamped from 0)"
+    );
+
+    stat.remove_override(Override::with_metadata(0., "petrify"));
+    assert!(!stat.contains(petrify));
+    assert_eq!(stat.cache_value().cached(), Some(15.));
+}
+
+#[test]
+fn floors_and_ceilings() {
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.apply_mul(Multiplicative::from_raw(0.1));
+
+    stat.apply_floor(Floor::with_metadata(0.5, "boots"));
+    let ring = stat.apply_floor_from_shared(FloorAll::<f64, &str>::with_metadata(2., "ring"));
+
+    assert_eq!(stat.cache_value().cached(), Some(2.));
+
+    stat.remove_by_id(ring);
+    stat.apply_ceiling(Ceiling::with_metadata(30., "armor"));
+    stat.apply_ceiling_timed(Ceiling::from_raw(0.2), Duration::Turns(1));
+
+    assert_eq!(stat.cache_value().cached(), Some(0.2));
+    assert_eq!(
+        stat.breakdown().to_string(),
+        "base: 10
+stage 0:
+    x 0.1
+    = 1
+floor:
+    >= 0.5 (\"boots\")
+    = 1
+ceiling:
+    <= 30 (\"armor\")
+    <= 0.2
+    = 0.2
+final: 0.2"
+    );
+
+    stat.tick(Tick::Turns(1));
+    stat.apply_mul(Multiplicative::from_raw(100.));
+
+    assert_eq!(stat.cache_value().cached(), Some(30.));
+
+    stat.remove_with_metadata("armor")
+        .remove_floor(Floor::with_metadata(0.5, "boots"));
+
+    assert!(stat.floors().is_empty() && stat.ceilings().is_empty());
+    assert_eq!(stat.cache_value().cached(), Some(100.));
+}
+
+#[test]
+fn stacking() {
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.set_stacking("slow", Stacking::LowestOnly)
+        .set_stacking("aura", Stacking::Unique)
+        .set_stacking("rage", Stacking::HighestOnly)
+        .set_stacking("combo", Stacking::MaxStacks(2));
+
+    stat.apply_mul(Multiplicative::with_metadata(0.8, "slow"));
+    let strongest = stat.apply_mul(Multiplicative::with_metadata(0.5, "slow"));
+    stat.apply_mul(Multiplicative::with_metadata(0.9, "slow"));
+    stat.apply_add(Additive::with_metadata(0.5, "aura"));
+    stat.apply_add(Additive::with_metadata(0.5, "aura"));
+    stat.apply_flat(Flat::with_metadata(1., "rage"));
+    stat.apply_flat(Flat::with_metadata(3., "rage"));
+    for _ in 0..4 {
+        stat.apply_flat(Flat::with_metadata(1., "combo"));
+    }
+
+    // (10 + 3 + 2) * 1.5 * 0.5
+    assert_eq!(stat.cache_value().cached(), Some(11.25));
+    assert_eq!(stat.stacking("aura"), Stacking::Unique);
+
+    let breakdown = stat.breakdown();
+    let stage = &breakdown.stages[0];
+
+    assert_eq!(stage.flats.len(), 3);
+    assert_eq!(stage.suppressed_flats.len(), 3);
+    assert_eq!(stage.suppressed_additives.len(), 1);
+    assert_eq!(stage.multiplicatives[0].id(), Some(strongest));
+    assert_eq!(stage.suppressed_multiplicatives.len(), 2);
+
+    stat.remove_by_id(strongest);
+    assert_eq!(stat.cache_value().cached(), Some(18.));
+
+    stat.set_stacking("slow", Stacking::Stack)
+        .set_stacking("aura", Stacking::Stack);
+    assert_eq!(stat.stacking("slow"), Stacking::Stack);
+    // (10 + 3 + 2) * 2 * 0.8 * 0.9
+    assert_eq!(stat.cache_value().cached(), Some(30. * (0.8 * 0.9)));
+}
+
+#[test]
+fn stacking_breakdown() {
+    let mut stat = Stat::<Dummy>::with_base(1.);
+    stat.set_stacking("aura", Stacking::Unique);
+    stat.apply_flat(Flat::with_metadata(2., "aura"));
+    stat.apply_flat(Flat::with_metadata(3., "aura"));
+
+    assert_eq!(
+        stat.breakdown().to_string(),
+        "base: 1
+stage 0:
+    + 2 (\"aura\")
+    [suppressed] + 3 (\"aura\")
+    = 3
+final: 3"
+    );
+}
+
+#[test]
+fn curves() {
+    let mut stat = Stat::<Dummy>::with_base(0.);
+    stat.set_flat_curve(Curve::piecewise_linear([(0., 0.), (10., 10.), (30., 20.)]));
+    stat.apply_flat(Flat::from_raw(15.));
+    stat.apply_flat(Flat::from_raw(5.).at::<Last>());
+
+    // Curve is applied to totals of each stage separately.
+    assert_eq!(stat.cache_value().cached(), Some(17.5));
+    assert_eq!(stat.breakdown().stages[0].flat_sum, 12.5);
+
+    stat.clear_curves();
+    stat.set_additive_curve(Curve::exponential(1.));
+    for _ in 0..4 {
+        stat.apply_add(Additive::from_raw(0.25));
+    }
+
+    let breakdown = stat.breakdown();
+
+    assert_eq!(breakdown.stages[0].additive_sum, 1. - (-1f64).exp());
+    assert_eq!(stat.cache_value().cached(), Some(breakdown.value));
+    assert!(stat.additive_curve().is_some() && stat.flat_curve().is_none());
+
+    stat.set_additive_curve(Curve::from_fn(|_| 0.));
+    assert_eq!(stat.cache_value().cached(), Some(20.));
+
+    // Stages without flats are left alone.
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.set_flat_curve(Curve::from_fn(|x| x + 1.));
+    stat.apply_flat(Flat::from_raw(4.));
+    stat.apply_mul(Multiplicative::from_raw(2.).at::<Last>());
+
+    assert_eq!(stat.cache_value().cached(), Some(30.));
+    assert!(stat
+        .breakdown()
+        .to_string()
+        .contains("+ 4\n    ~ 5 (curve)\n    = 15"));
+}
+
+struct Context {
+    health: f64,
+    night: bool,
+}
+
+#[test]
+fn conditional() {
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.set_stacking("aura", Stacking::Unique);
+
+    stat.apply_add_if(Additive::from_raw(0.2), |ctx: &Context| ctx.health < 0.5);
+    let night = stat.apply_flat_if(Flat::with_metadata(3., "aura"), |ctx: &Context| ctx.night);
+    stat.apply_flat(Flat::with_metadata(1., "aura"));
+    stat.apply_ceiling_if(Ceiling::from_raw(12.), |ctx: &Context| ctx.night);
+    stat.apply_override_if(Override::from_raw(0.), |_: &u32| true);
+
+    let mut ctx = Context {
+        health: 1.,
+        night: false,
+    };
+
+    // Inactive conditional modifiers don't take part in stacking.
+    assert_eq!(stat.evaluate_with(&ctx), 11.);
+    assert_eq!(stat.cache_value().cached(), Some(11.));
+    assert!(!stat.is_stale(&ctx));
+
+    ctx.night = true;
+    ctx.health = 0.25;
+    assert!(stat.is_stale(&ctx));
+    assert_eq!(stat.evaluate_with(&ctx), 12.);
+    assert_eq!(stat.evaluate_with(&0u32), 0.);
+
+    stat.remove_by_id(night);
+    assert!(stat.is_stale(&ctx));
+    assert_eq!(stat.evaluate_with(&ctx), 12.);
+
+    ctx.night = false;
+    assert_eq!(stat.evaluate_with(&ctx), 11. * 1.2);
+    assert_eq!(stat.breakdown().stages[0].suppressed_additives.len(), 1);
+}
+
+struct Formulated<F>(PhantomData<F>);
+
+impl<F: Formula<f64>> StatMarker for Formulated<F> {
+    type Raw = f64;
+
+    type Metadata = ();
+
+    type Formula = F;
+}
+
+fn formulated<F: Formula<f64>>() -> Stat<Formulated<F>> {
+    let mut stat = Stat::with_base(10.);
+    stat.apply_flat(Flat::from_raw(10.));
+    stat.apply_add(Additive::from_raw(0.5));
+    stat.apply_mul(Multiplicative::from_raw(0.5));
+    stat.apply_mul(Multiplicative::from_raw(1.));
+    stat.cache_value();
+    stat
+}
+
+#[test]
+fn formulas() {
+    assert_eq!(formulated::<Standard>().cached(), Some(15.));
+    assert_eq!(formulated::<IncreasedMore>().cached(), Some(90.));
+    assert_eq!(formulated::<AdditivePercent>().cached(), Some(20.));
+
+    let mut stat = formulated::<FlatsLast>();
+    assert_eq!(stat.cached(), Some(17.5));
+
+    let breakdown = stat.breakdown();
+    assert_eq!(breakdown.stages[0].after_flats, 20.);
+    assert_eq!(breakdown.stages[0].after_additives, 25.);
+    assert_eq!(breakdown.value, 17.5);
+
+    stat.apply_flat(Flat::from_raw(5.).at::<Last>());
+    assert_eq!(stat.cache_value().cached(), Some(22.5));
+}
+
+#[test]
+fn batch() {
+    let (sender, receiver) = mpsc::channel();
+    let mut stat = Stat::<Dummy>::with_base(10.);
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    let sword = stat
+        .batch(|stat| {
+            let sword = stat.apply_flat(Flat::with_metadata(5., "sword"));
+            stat.apply_add_timed(Additive::with_metadata(1., "sword"), Duration::Turns(2));
+            stat.set_stacking("sword", Stacking::Unique);
+            Ok::<_, ()>(sword)
+        })
+        .unwrap();
+
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(10., 30.)]);
+
+    // Nested batches notify once, when the outer one ends.
+    let err = stat.batch(|stat| {
+        stat.remove_by_id(sword);
+        stat.batch(|stat| {
+            stat.set_base(0.);
+            Ok::<_, &str>(())
+        })?;
+        stat.tick(Tick::Turns(2));
+        if stat.contains(sword) {
+            Ok(())
+        } else {
+            Err("sword is gone")
+        }
+    });
+
+    assert_eq!(err, Err("sword is gone"));
+    assert!(receiver.try_iter().next().is_none());
+    assert_eq!(stat.cache_value().cached(), Some(30.));
+    assert_eq!(stat.base(), 10.);
+    assert_eq!(
+        stat.remaining(stat.additives()[0].id().unwrap()),
+        Some(Duration::Turns(2))
+    );
+    assert_eq!(stat.stacking("sword"), Stacking::Unique);
+
+    stat.batch(|stat| {
+        stat.remove_with_metadata("sword");
+        stat.apply_flat(Flat::from_raw(0.));
+        Ok::<_, ()>(())
+    })
+    .unwrap();
+
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(30., 10.)]);
+}
+
+#[test]
+fn batch_panic() {
+    let (sender, receiver) = mpsc::channel();
+    let mut stat = Stat::<Dummy>::with_base(2.);
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    let panicked = panic::catch_unwind(AssertUnwindSafe(|| {
+        stat.batch(|stat| {
+            stat.apply_flat(Flat::from_raw(1.));
+            panic!("batch failed");
+            #[allow(unreachable_code)]
+            Ok::<_, ()>(())
+        })
+    }));
+
+    assert!(panicked.is_err());
+    assert!(stat.flats().is_empty());
+    assert_eq!(stat.cache_value().cached(), Some(2.));
+
+    stat.apply_flat(Flat::from_raw(10.));
+    stat.cache_value();
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(2., 12.)]);
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "async")]
+
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use mini_stat::{
+    asynchronous::MiniStat,
+    curve::Curve,
+    prelude::*,
+    timed::{Duration, Tick},
+};
+use tokio::task;
+
+#[derive(Debug, Default)]
+struct Dummy;
+
+impl StatMarker for Dummy {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[tokio::test]
+async fn specs() {
+    let stat = MiniStat::<Dummy>::with_base(3.);
+
+    let flat = Flat::with_metadata(1., "ring");
+    stat.apply_flat(flat).await;
+    stat.apply_add(Additive::from_raw(1.)).await;
+    let id = stat.apply_mul(Multiplicative::from_raw(3.)).await;
+    assert_eq!(stat.cached().await, 24.);
+
+    stat.remove_by_id(id).await;
+    stat.remove_flat(flat).await;
+    assert_eq!(stat.cached().await, 6.);
+
+    stat.set_base(4.).await;
+    assert_eq!(stat.base().await, 4.);
+    assert_eq!(stat.breakdown().await.value, 8.);
+
+    stat.apply_flat_timed(Flat::from_raw(2.), Duration::Turns(2))
+        .await;
+    assert_eq!(stat.cached().await, 12.);
+    assert_eq!(stat.tick(Tick::Turns(2)).await.len(), 1);
+    assert_eq!(stat.cached().await, 8.);
+}
+
+#[tokio::test]
+async fn changes() {
+    let stat = Rc::new(MiniStat::<Dummy>::with_base(1.));
+    let mut changes = stat.changes();
+
+    let local = task::LocalSet::new();
+    local
+        .run_until(async {
+            let waiter = task::spawn_local(async move {
+                let mut seen = Vec::new();
+                while let Some(value) = changes.next().await {
+                    seen.push(value);
+                }
+                seen
+            });
+
+            let writer = Rc::clone(&stat);
+            task::spawn_local(async move {
+                let id = writer.apply_flat(Flat::from_raw(1.)).await;
+                task::yield_now().await;
+                // Doesn't change the final value, so nobody gets woken.
+                writer.apply_add(Additive::from_raw(0.)).await;
+                task::yield_now().await;
+                writer.remove_by_id(id).await;
+                task::yield_now().await;
+            })
+            .await
+            .unwrap();
+
+            drop(stat);
+            assert_eq!(waiter.await.unwrap(), [2., 1.]);
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn guard_across_await() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+    let mut changes = stat.changes();
+
+    let mut guard = stat.stat_mut().await;
+    guard.apply_flat(Flat::from_raw(1.));
+    task::yield_now().await;
+    guard.apply_flat(Flat::from_raw(1.));
+    drop(guard);
+
+    assert_eq!(changes.next().await, Some(3.));
+    assert_eq!(changes.current(), 3.);
+    assert_eq!(stat.cached().await, 3.);
+}
+
+#[tokio::test]
+async fn panic_in_guard() {
+    let armed = Arc::new(AtomicBool::new(false));
+    let stat = Arc::new(MiniStat::<Dummy>::with_base(1.));
+    stat.stat_mut().await.set_flat_curve(Curve::from_fn({
+        let armed = armed.clone();
+        move |sum| {
+            assert!(
+                !armed.load(Ordering::SeqCst),
+                "curve evaluated while unwinding"
+            );
+            sum
+        }
+    }));
+
+    let panicked = tokio::spawn({
+        let stat = stat.clone();
+        let armed = armed.clone();
+        async move {
+            let mut guard = stat.stat_mut().await;
+            guard.apply_flat(Flat::from_raw(1.));
+            armed.store(true, Ordering::SeqCst);
+            panic!("panic while holding the guard");
+        }
+    })
+    .await;
+
+    assert!(panicked.is_err());
+    armed.store(false, Ordering::SeqCst);
+    assert_eq!(stat.cached().await, 2.);
+}
+
+#[tokio::test]
+async fn batch() {
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    let mut changes = stat.changes();
+
+    stat.batch(|stat| {
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_mul(Multiplicative::from_raw(2.));
+        Ok::<_, ()>(())
+    })
+    .await
+    .unwrap();
+
+    assert!(stat
+        .batch(|stat| {
+            stat.set_base(0.);
+            Err::<(), _>(())
+        })
+        .await
+        .is_err());
+
+    assert_eq!(changes.next().await, Some(6.));
+    assert_eq!(stat.cached().await, 6.);
+}
@@ -0,0 +1,372 @@
+use mini_stat::{
+    derived::{GraphError, Source, StatGraph},
+    prelude::*,
+};
+
+#[derive(Debug, Default)]
+struct Constitution;
+
+impl StatMarker for Constitution {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Level;
+
+impl StatMarker for Level {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct MaxHealth;
+
+impl StatMarker for MaxHealth {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Agility;
+
+impl StatMarker for Agility {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct CritChance;
+
+impl StatMarker for CritChance {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Mana;
+
+impl StatMarker for Mana {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct EnergyShield;
+
+impl StatMarker for EnergyShield {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Physical;
+
+impl StatMarker for Physical {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Fire;
+
+impl StatMarker for Fire {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Cold;
+
+impl StatMarker for Cold {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[test]
+fn derived_base() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Constitution>::with_base(5.))
+        .insert(Stat::<Level>::with_base(3.))
+        .insert(Stat::<MaxHealth>::with_base(0.));
+
+    graph
+        .derive_base::<MaxHealth>(
+            &[Source::of::<Constitution>(), Source::of::<Level>()],
+            |s| 10. * s.value::<Constitution>() + s.value::<Level>(),
+        )
+        .unwrap();
+
+    assert_eq!(graph.get::<MaxHealth>().unwrap().cached(), None);
+    assert_eq!(graph.value::<MaxHealth>(), Some(53.));
+
+    graph.update::<Constitution, _>(|stat| stat.apply_add(Additive::from_raw(1.)));
+
+    assert_eq!(graph.get::<MaxHealth>().unwrap().cached(), None);
+    assert_eq!(graph.value::<MaxHealth>(), Some(103.));
+
+    graph.update::<MaxHealth, _>(|stat| stat.apply_mul(Multiplicative::from_raw(2.)));
+
+    assert_eq!(graph.value::<MaxHealth>(), Some(206.));
+}
+
+#[test]
+fn derived_modifiers_chain() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Level>::with_base(10.))
+        .insert(Stat::<Agility>::with_base(0.))
+        .insert(Stat::<CritChance>::with_base(5.));
+
+    graph
+        .derive_base::<Agility>(&[Source::of::<Level>()], |s| 2. * s.value::<Level>())
+        .unwrap()
+        .derive_flat::<CritChance>(&[Source::of::<Agility>()], |s| {
+            Flat::with_metadata((s.value::<Agility>() / 10.).floor(), "agility")
+        })
+        .unwrap();
+
+    assert_eq!(graph.value::<CritChance>(), Some(7.));
+
+    graph.update::<Level, _>(|stat| stat.apply_flat(Flat::from_raw(5.)));
+
+    assert_eq!(graph.value::<CritChance>(), Some(8.));
+    assert_eq!(
+        graph
+            .get::<CritChance>()
+            .unwrap()
+            .flats_with_metadata("agility")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn cycles_are_rejected() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Level>::with_base(1.))
+        .insert(Stat::<Agility>::with_base(1.))
+        .insert(Stat::<CritChance>::with_base(1.));
+
+    graph
+        .derive_base::<Agility>(&[Source::of::<Level>()], |s| s.value::<Level>())
+        .unwrap()
+        .derive_base::<CritChance>(&[Source::of::<Agility>()], |s| s.value::<Agility>())
+        .unwrap();
+
+    let err = graph
+        .derive_flat::<Level>(&[Source::of::<CritChance>()], |s| {
+            Flat::from_raw(s.value::<CritChance>())
+        })
+        .unwrap_err();
+
+    assert!(matches!(err, GraphError::Cycle(ref path) if path.len() == 4));
+
+    let err = graph
+        .derive_base::<Level>(&[Source::of::<Level>()], |s| s.value::<Level>())
+        .unwrap_err();
+
+    assert!(matches!(err, GraphError::Cycle(_)));
+
+    let err = graph
+        .derive_base::<MaxHealth>(&[Source::of::<Level>()], |s| s.value::<Level>())
+        .unwrap_err();
+
+    assert!(matches!(err, GraphError::MissingStat(_)));
+
+    assert_eq!(graph.value::<CritChance>(), Some(1.));
+}
+
+#[test]
+fn derived_bounds() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Level>::with_base(2.))
+        .insert(Stat::<CritChance>::with_base(50.));
+
+    graph
+        .derive_max::<CritChance>(&[Source::of::<Level>()], |s| 10. * s.value::<Level>())
+        .unwrap();
+
+    assert_eq!(graph.value::<CritChance>(), Some(20.));
+    assert!(graph.get::<CritChance>().unwrap().is_clamped());
+
+    graph.update::<Level, _>(|stat| stat.apply_flat(Flat::from_raw(8.)));
+
+    assert_eq!(graph.value::<CritChance>(), Some(50.));
+    assert!(!graph.get::<CritChance>().unwrap().is_clamped());
+}
+
+#[test]
+fn gain_as() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Mana>::with_base(200.))
+        .insert(Stat::<EnergyShield>::with_base(50.));
+
+    graph.gain_as::<Mana, EnergyShield>(0.25).unwrap();
+
+    assert_eq!(graph.value::<Mana>(), Some(200.));
+    assert_eq!(graph.value::<EnergyShield>(), Some(100.));
+
+    graph.update::<Mana, _>(|stat| stat.apply_add(Additive::from_raw(1.)));
+
+    assert_eq!(graph.value::<EnergyShield>(), Some(150.));
+}
+
+#[test]
+fn conversion() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Physical>::with_base(80.))
+        .insert(Stat::<Fire>::with_base(0.))
+        .insert(Stat::<Cold>::with_base(0.));
+
+    graph.update::<Physical, _>(|stat| stat.apply_flat(Flat::from_raw(20.)));
+    graph.update::<Fire, _>(|stat| stat.apply_add(Additive::from_raw(0.5)));
+    graph.convert::<Physical, Fire>(0.25).unwrap();
+    graph.convert::<Physical, Cold>(0.5).unwrap();
+
+    assert_eq!(graph.value::<Physical>(), Some(25.));
+    assert_eq!(graph.value::<Fire>(), Some(37.5));
+    assert_eq!(graph.value::<Cold>(), Some(50.));
+
+    graph.update::<Physical, _>(|stat| {
+        stat.set_base(180.);
+    });
+
+    assert_eq!(graph.value::<Physical>(), Some(50.));
+    assert_eq!(graph.value::<Fire>(), Some(75.));
+    assert_eq!(graph.value::<Cold>(), Some(100.));
+}
+
+#[test]
+fn conversion_with_context() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Physical>::with_base(100.))
+        .insert(Stat::<Fire>::with_base(0.));
+
+    graph.update::<Physical, _>(|stat| {
+        stat.apply_mul_if(Multiplicative::from_raw(2.), |enraged: &bool| *enraged);
+    });
+    graph.convert::<Physical, Fire>(0.5).unwrap();
+
+    assert_eq!(graph.value::<Physical>(), Some(50.));
+    assert_eq!(
+        graph.update::<Physical, _>(|stat| stat.evaluate_with(&false)),
+        Some(50.)
+    );
+    assert_eq!(
+        graph.update::<Physical, _>(|stat| stat.evaluate_with(&true)),
+        Some(100.)
+    );
+}
+
+#[test]
+fn conversion_conserves_total() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Physical>::with_base(100.))
+        .insert(Stat::<Fire>::with_base(0.))
+        .insert(Stat::<Cold>::with_base(0.));
+
+    graph.update::<Physical, _>(|stat| stat.apply_add(Additive::from_raw(0.5).at::<Last>()));
+    graph.convert::<Physical, Fire>(0.2).unwrap();
+
+    assert_eq!(graph.value::<Physical>(), Some(120.));
+    assert_eq!(graph.value::<Fire>(), Some(30.));
+
+    // Floors and overrides of the source don't cancel the conversion.
+    let floor = graph
+        .update::<Physical, _>(|stat| stat.apply_floor(Floor::from_raw(200.)))
+        .unwrap();
+
+    assert_eq!(graph.value::<Physical>(), Some(160.));
+    assert_eq!(graph.value::<Fire>(), Some(40.));
+
+    graph.update::<Physical, _>(|stat| {
+        stat.remove_by_id(floor);
+        stat.apply_override(Override::from_raw(50.));
+    });
+
+    assert_eq!(graph.value::<Physical>(), Some(40.));
+    assert_eq!(graph.value::<Fire>(), Some(10.));
+
+    // Conversions are kept, when the source gets replaced.
+    graph.insert(Stat::<Physical>::with_base(10.));
+
+    assert_eq!(graph.value::<Physical>(), Some(8.));
+    assert_eq!(graph.value::<Fire>(), Some(2.));
+
+    graph.convert::<Physical, Cold>(0.8).unwrap();
+
+    assert_eq!(graph.value::<Physical>(), Some(0.));
+    assert_eq!(graph.value::<Cold>(), Some(8.));
+    assert!(graph
+        .get::<Physical>()
+        .unwrap()
+        .breakdown()
+        .to_string()
+        .ends_with("converted: 10 -> 0\nfinal: 0"));
+    assert_eq!(
+        graph.convert::<Physical, Cold>(0.1).unwrap_err(),
+        GraphError::Overconverted(std::any::type_name::<Physical>())
+    );
+}
+
+#[test]
+fn conversion_cycles_are_rejected() {
+    let mut graph = StatGraph::<2>::new();
+    graph
+        .insert(Stat::<Physical>::with_base(100.))
+        .insert(Stat::<Fire>::with_base(0.))
+        .insert(Stat::<Cold>::with_base(0.));
+
+    graph.convert::<Physical, Fire>(0.5).unwrap();
+    graph.gain_as::<Fire, Cold>(0.5).unwrap();
+
+    let err = graph.convert::<Cold, Physical>(0.5).unwrap_err();
+
+    assert!(matches!(err, GraphError::Cycle(_)));
+    assert!(matches!(
+        graph.convert::<Physical, Physical>(0.5),
+        Err(GraphError::Cycle(_))
+    ));
+
+    assert_eq!(graph.value::<Physical>(), Some(50.));
+    assert_eq!(graph.value::<Fire>(), Some(50.));
+    assert_eq!(graph.value::<Cold>(), Some(25.));
+}
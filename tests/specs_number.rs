@@ -0,0 +1,153 @@
+use std::ops::{Add, Mul};
+
+use mini_stat::{number::Fixed, prelude::*};
+
+struct Level;
+
+impl StatMarker for Level {
+    type Raw = i32;
+    type Metadata = ();
+    type Formula = Standard;
+}
+
+struct Experience;
+
+impl StatMarker for Experience {
+    type Raw = i64;
+    type Metadata = ();
+    type Formula = Standard;
+}
+
+struct Speed;
+
+impl StatMarker for Speed {
+    type Raw = Fixed;
+    type Metadata = ();
+    type Formula = Standard;
+}
+
+/// Money in cents, saturating instead of overflowing.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Cents(u32);
+
+impl Add for Cents {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Mul for Cents {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0.saturating_mul(rhs.0))
+    }
+}
+
+impl StatNumber for Cents {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+}
+
+struct Price;
+
+impl StatMarker for Price {
+    type Raw = Cents;
+    type Metadata = ();
+    type Formula = Standard;
+}
+
+#[test]
+fn integer() {
+    let mut level = Stat::<Level>::with_base(10);
+    level.apply_flat(Flat::from_raw(5));
+    level.apply_add(Additive::from_raw(1));
+    level.apply_mul(Multiplicative::from_raw(3));
+
+    assert_eq!(level.cache_value().cached(), Some(90));
+
+    let mut experience = Stat::<Experience>::with_base(1 << 40);
+    experience.apply_flat(Flat::from_raw(-7));
+    experience.apply_mul(Multiplicative::from_raw(3));
+
+    assert_eq!(experience.cache_value().cached(), Some(3_298_534_883_307));
+}
+
+#[test]
+fn integer_resource() {
+    let mut health = Resource::<Level>::with_max(100);
+    health.damage(30);
+    health.max_mut().apply_mul(Multiplicative::from_raw(2));
+
+    assert_eq!(health.max(), 200);
+    assert_eq!(health.current(), 70);
+}
+
+#[test]
+fn fixed_arithmetic() {
+    assert_eq!(Fixed::ONE.to_bits(), 0x0001_0000);
+    assert_eq!(Fixed::from_ratio(1, 3).to_bits(), 21_845);
+    assert_eq!(Fixed::from_ratio(-1, 3).to_bits(), -21_846);
+    assert_eq!(
+        (Fixed::from_ratio(-3, 2) * Fixed::from_ratio(1, 3)).to_bits(),
+        -32_768
+    );
+    assert_eq!((Fixed::from_int(1) / Fixed::from_int(3)).to_bits(), 21_845);
+    assert_eq!(Fixed::from_ratio(7, 2).to_int(), 3);
+    assert_eq!(Fixed::from_ratio(-7, 2).to_int(), -4);
+}
+
+#[test]
+fn fixed_overflow() {
+    let big = Fixed::from_int(30_000);
+
+    assert_eq!(Fixed::from_int(40_000), Fixed::MAX);
+    assert_eq!(Fixed::from_int(-40_000), Fixed::MIN);
+    assert_eq!(big + big, Fixed::MAX);
+    assert_eq!(-big - big, Fixed::MIN);
+    assert_eq!(big * Fixed::from_int(-2), Fixed::MIN);
+    assert_eq!(big / Fixed::from_ratio(1, 2), Fixed::MAX);
+    assert_eq!(-Fixed::MIN, Fixed::MAX);
+
+    assert_eq!(Fixed::from_ratio(1, 0), Fixed::MAX);
+    assert_eq!(Fixed::from_ratio(-1, 0), Fixed::MIN);
+    assert_eq!(Fixed::from_ratio(0, 0), Fixed::ZERO);
+    assert_eq!(big / Fixed::ZERO, Fixed::MAX);
+
+    assert_eq!(big.checked_add(big), None);
+    assert_eq!(big.checked_sub(-big), None);
+    assert_eq!(big.checked_mul(Fixed::from_int(2)), None);
+    assert_eq!(big.checked_div(Fixed::ZERO), None);
+    assert_eq!(big.checked_div(Fixed::from_ratio(1, 2)), None);
+    assert_eq!(
+        big.checked_div(Fixed::from_int(2)),
+        Some(Fixed::from_int(15_000))
+    );
+}
+
+#[test]
+fn fixed() {
+    let mut speed = Stat::<Speed>::with_base(Fixed::from_int(10));
+    speed.apply_flat(Flat::from_raw(Fixed::from_ratio(1, 3)));
+    speed.apply_add(Additive::from_raw(Fixed::from_ratio(1, 10)));
+    speed.apply_mul(Multiplicative::from_raw(Fixed::from_ratio(3, 2)));
+
+    let value = speed.cache_value().cached().unwrap();
+
+    assert_eq!(value.to_bits(), 1_117_378);
+    assert_eq!(value.to_int(), 17);
+}
+
+#[test]
+fn custom_number() {
+    let mut price = Stat::<Price>::with_base(Cents(u32::MAX - 10));
+    price.apply_flat(Flat::from_raw(Cents(5)));
+
+    assert_eq!(price.cache_value().cached(), Some(Cents(u32::MAX - 5)));
+
+    price.apply_mul(Multiplicative::from_raw(Cents(2)));
+
+    assert_eq!(price.cache_value().cached(), Some(Cents(u32::MAX)));
+}
@@ -1,4 +1,14 @@
-use mini_stat::{prelude::*, refcell::MiniStat};
+#![cfg(feature = "refcell")]
+
+use std::{cell::Cell, rc::Rc, sync::mpsc};
+
+use mini_stat::{
+    error::Error,
+    prelude::*,
+    refcell::MiniStat,
+    stat::observer::LocalCallback,
+    timed::{Duration, Tick},
+};
 
 #[derive(Debug, Default)]
 struct Dummy;
@@ -7,6 +17,8 @@ impl StatMarker for Dummy {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 #[test]
@@ -14,10 +26,12 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(3.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_flat(Flat::from_raw(1.))
-        .apply_flat(Flat::from_raw(2.))
-        .apply_flat(Flat::from_raw(3.));
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_flat(Flat::from_raw(2.));
+        stat.apply_flat(Flat::from_raw(3.));
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -26,10 +40,12 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(6.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.3));
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_add(Additive::from_raw(0.1));
+        stat.apply_add(Additive::from_raw(0.1));
+        stat.apply_add(Additive::from_raw(0.3));
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -38,11 +54,13 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(1.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_mul(Multiplicative::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(3.))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_mul(Multiplicative::from_raw(0.5));
+        stat.apply_mul(Multiplicative::from_raw(3.));
+        stat.apply_mul(Multiplicative::from_raw(6.));
+        stat.cache_value();
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -51,11 +69,13 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(0.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_flat(Flat::from_raw(1.))
-        .apply_add(Additive::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_add(Additive::from_raw(0.5));
+        stat.apply_mul(Multiplicative::from_raw(6.));
+        stat.cache_value();
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -70,3 +90,203 @@ fn specs() {
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 }
+
+#[test]
+fn remove_by_id() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+
+    let first = stat.apply_flat(Flat::from_raw(5.));
+    let second = stat.apply_flat(Flat::from_raw(5.));
+
+    assert_eq!(stat.cached(), 11.);
+
+    stat.remove_by_id(first);
+
+    assert_eq!(stat.cached(), 6.);
+    assert_eq!(stat.stat_mut().flats()[0].id(), Some(second));
+}
+
+#[test]
+fn timed() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+
+    let buff = stat.apply_flat_timed(Flat::from_raw(2.), Duration::Turns(2));
+
+    assert_eq!(stat.cached(), 3.);
+    assert!(stat.tick(Tick::Turns(1)).is_empty());
+    assert_eq!(stat.remaining(buff), Some(Duration::Turns(1)));
+    assert_eq!(stat.tick(Tick::Turns(1)).as_slice(), &[buff]);
+    assert_eq!(stat.cached(), 1.);
+}
+
+#[test]
+fn resource() {
+    let mana = MiniResource::<Dummy>::with_max(50.);
+
+    assert!(mana.spend(20.));
+    assert_eq!(mana.current(), 30.);
+
+    mana.resource_mut()
+        .max_mut()
+        .apply_flat(Flat::from_raw(-40.));
+
+    assert_eq!(mana.max(), 10.);
+    assert_eq!(mana.current(), 10.);
+    assert_eq!(mana.damage(15.), 10.);
+    assert_eq!(mana.heal(5.), 5.);
+}
+
+#[test]
+fn observers() {
+    let (sender, receiver) = mpsc::channel();
+    let stat = MiniStat::<Dummy>::with_base(2.);
+
+    let observer = stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+    let buff = stat.apply_mul(Multiplicative::from_raw(3.));
+    stat.remove_by_id(buff);
+    stat.unsubscribe(observer);
+    stat.apply_flat(Flat::from_raw(1.));
+
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        [(2., 6.), (6., 2.)]
+    );
+}
+
+#[test]
+fn local_observers() {
+    let last = Rc::new(Cell::new(0.));
+    let stat = MiniStat::<Dummy, 2, LocalCallback<f64>>::with_base(2.);
+
+    stat.subscribe({
+        let last = last.clone();
+        move |_, new| last.set(new)
+    });
+    stat.apply_mul(Multiplicative::from_raw(3.));
+
+    assert_eq!(last.get(), 6.);
+}
+
+#[test]
+fn base() {
+    let stat = MiniStat::<Dummy>::with_base(4.);
+    stat.apply_flat(Flat::from_raw(1.));
+
+    stat.set_base(5.);
+    assert_eq!(stat.cached(), 6.);
+
+    stat.add_to_base(1.);
+    stat.map_base(|base| base * 2.);
+    assert_eq!(stat.base(), 12.);
+    assert_eq!(stat.cached(), 13.);
+}
+
+#[test]
+fn conditional() {
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    stat.apply_mul_if(Multiplicative::from_raw(3.), |night: &bool| *night);
+
+    assert!(stat.is_stale(&true));
+    assert_eq!(stat.evaluate_with(&true), 6.);
+    assert!(!stat.is_stale(&true));
+    assert_eq!(stat.evaluate_with(&false), 2.);
+    assert_eq!(stat.cached(), 2.);
+}
+
+#[test]
+fn borrow_conflicts() {
+    let stat = MiniStat::<Dummy>::with_base(2.);
+
+    let guard = stat.stat_mut();
+    assert_eq!(
+        stat.try_apply_flat(Flat::from_raw(1.)),
+        Err(Error::BorrowConflict)
+    );
+    assert_eq!(stat.try_cached(), Err(Error::BorrowConflict));
+    assert!(stat.try_stat().is_err());
+    drop(guard);
+
+    let id = stat.try_apply_flat(Flat::from_raw(1.)).unwrap();
+    let guard = stat.stat();
+    assert_eq!(stat.try_cached(), Err(Error::CacheMissing));
+    assert!(stat.try_stat_mut().is_err());
+    drop(guard);
+
+    assert_eq!(stat.try_cached(), Ok(3.));
+    let guard = stat.stat();
+    assert_eq!(stat.try_cached(), Ok(3.));
+    assert_eq!(stat.try_remove_by_id(id), Err(Error::BorrowConflict));
+    drop(guard);
+
+    stat.try_remove_by_id(id).unwrap();
+    assert_eq!(stat.try_cached(), Ok(2.));
+
+    let guard = stat.stat();
+    assert_eq!(stat.try_set_base(4.), Err(Error::BorrowConflict));
+    assert_eq!(
+        stat.try_apply_add_timed(Additive::from_raw(1.), Duration::Turns(1)),
+        Err(Error::BorrowConflict)
+    );
+    assert_eq!(
+        stat.try_apply_mul_if(Multiplicative::from_raw(2.), |_: &bool| true),
+        Err(Error::BorrowConflict)
+    );
+    assert_eq!(stat.try_tick(Tick::Turns(1)), Err(Error::BorrowConflict));
+    assert_eq!(
+        stat.try_batch(|_| Ok::<_, ()>(())),
+        Err(Error::BorrowConflict)
+    );
+    drop(guard);
+
+    assert_eq!(
+        stat.try_batch(|stat| stat.set_base(4.).cache_value().cached().ok_or(())),
+        Ok(Ok(4.))
+    );
+}
+
+#[test]
+fn reentrant_observer() {
+    let conflicts = Rc::new(Cell::new(0));
+    let stat = Rc::new(MiniStat::<Dummy, 2, LocalCallback<f64>>::with_base(2.));
+
+    stat.subscribe({
+        let stat = Rc::downgrade(&stat);
+        let conflicts = conflicts.clone();
+        move |_, new| {
+            let stat = stat.upgrade().unwrap();
+            assert_eq!(stat.try_set_base(new * 2.), Err(Error::BorrowConflict));
+            assert_eq!(
+                stat.try_remove_with_metadata("buff"),
+                Err(Error::BorrowConflict)
+            );
+            conflicts.set(conflicts.get() + 1);
+        }
+    });
+    stat.apply_flat(Flat::from_raw(1.));
+
+    assert_eq!(conflicts.get(), 1);
+    assert_eq!(stat.cached(), 3.);
+}
+
+#[test]
+fn batch() {
+    let (sender, receiver) = mpsc::channel();
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    stat.batch(|stat| {
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_mul(Multiplicative::from_raw(2.));
+        Ok::<_, ()>(())
+    })
+    .unwrap();
+
+    let err = stat.batch(|stat| {
+        stat.set_base(0.);
+        Err::<(), _>(())
+    });
+
+    assert!(err.is_err());
+    assert_eq!(stat.cached(), 6.);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(2., 6.)]);
+}
@@ -0,0 +1,112 @@
+use std::sync::{Arc, Mutex};
+
+use mini_stat::{bound::Bound, prelude::*, resource::MaxPolicy};
+
+#[derive(Debug, Default)]
+struct Health;
+
+impl StatMarker for Health {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[test]
+fn operations() {
+    let mut health = Resource::<Health>::with_max(100.);
+
+    assert!(health.is_full());
+    assert_eq!(health.damage(30.), 30.);
+    assert_eq!(health.current(), 70.);
+    assert_eq!(health.heal(50.), 30.);
+    assert_eq!(health.current(), 100.);
+
+    assert!(health.spend(60.));
+    assert!(!health.spend(60.));
+    assert_eq!(health.current(), 40.);
+
+    assert_eq!(health.damage(100.), 40.);
+    assert!(health.is_depleted());
+
+    health.set_regen_rate(5.);
+    assert_eq!(health.regen(2.), 10.);
+    assert_eq!(health.current(), 10.);
+    assert_eq!(health.percent(), 0.1);
+
+    health.fill();
+    assert_eq!(health.current(), 100.);
+}
+
+#[test]
+fn max_policies() {
+    let mut health = Resource::<Health>::with_max(100.);
+    health.damage(50.);
+
+    health.set_policy(MaxPolicy::KeepPercent);
+    let buff = health.max_mut().apply_mul(Multiplicative::from_raw(2.));
+
+    assert_eq!(health.max(), 200.);
+    assert_eq!(health.current(), 100.);
+
+    health.set_policy(MaxPolicy::Clamp);
+    health.max_mut().remove_by_id(buff);
+
+    assert_eq!(health.max(), 100.);
+    assert_eq!(health.current(), 100.);
+
+    health.max_mut().apply_flat(Flat::from_raw(-20.));
+
+    assert_eq!(health.current(), 80.);
+
+    health.set_policy(MaxPolicy::KeepAbsolute);
+    health.max_mut().apply_flat(Flat::from_raw(40.));
+
+    assert_eq!(health.max(), 120.);
+    assert_eq!(health.current(), 80.);
+
+    health.max_mut().apply_flat(Flat::from_raw(-60.));
+
+    assert_eq!(health.max(), 60.);
+    assert_eq!(health.current(), 80.);
+    assert_eq!(health.heal(10.), 0.);
+}
+
+#[test]
+fn invalid_amounts() {
+    let mut health = Resource::<Health>::with_max(100.);
+    health.damage(50.);
+
+    assert_eq!(health.damage(-20.), 0.);
+    assert_eq!(health.heal(-20.), 0.);
+    assert!(!health.spend(-20.));
+    assert_eq!(health.current(), 50.);
+
+    health.max_mut().apply_mul(Multiplicative::from_raw(0.));
+
+    assert_eq!(health.percent(), 0.);
+}
+
+#[test]
+fn dynamic_max() {
+    let cap = Arc::new(Mutex::new(100.));
+    let mut health = Resource::<Health>::with_max(200.);
+    health.max_mut().set_max(Bound::from_fn({
+        let cap = cap.clone();
+        move || *cap.lock().unwrap()
+    }));
+
+    assert_eq!(health.max(), 100.);
+
+    *cap.lock().unwrap() = 50.;
+    health.refresh();
+
+    assert_eq!(health.max(), 50.);
+    assert_eq!(health.current(), 50.);
+
+    *cap.lock().unwrap() = 80.;
+
+    assert_eq!(health.heal(100.), 30.);
+    assert_eq!(health.max(), 80.);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+use mini_stat::{
+    bound::Bound,
+    curve::Curve,
+    modifier::ModifierId,
+    prelude::*,
+    timed::{Duration, Tick},
+};
+
+#[derive(Debug, Default)]
+struct Dummy;
+
+impl StatMarker for Dummy {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Owned;
+
+impl StatMarker for Owned {
+    type Raw = f64;
+
+    type Metadata = u32;
+
+    type Formula = Standard;
+}
+
+#[test]
+fn round_trip() {
+    let mut stat = Stat::<Owned>::with_base(2.);
+    stat.set_max(20.);
+    let flat = stat.apply_flat(Flat::with_metadata(3., 7));
+    stat.apply_mul(Multiplicative::from_raw(3.).at::<Last>());
+    let buff = stat.apply_add_timed(Additive::from_raw(1.), Duration::Turns(2));
+
+    let json = serde_json::to_string(&stat).unwrap();
+
+    assert!(!json.contains("cached"));
+
+    let mut loaded: Stat<Owned> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded.cached(), Some(20.));
+    assert_eq!(loaded.unclamped(), Some(30.));
+    assert_eq!(loaded.flats_with_metadata(7).count(), 1);
+    assert_eq!(loaded.remaining(buff), Some(Duration::Turns(2)));
+
+    let new = loaded.apply_flat(Flat::from_raw(1.));
+
+    assert_ne!(new, flat);
+    assert_ne!(new, buff);
+
+    loaded.remove_by_id(flat);
+    loaded.tick(Tick::Turns(2));
+
+    assert_eq!(loaded.cache_value().cached(), Some(9.));
+}
+
+#[test]
+fn modifiers() {
+    let modifier = Flat::<Dummy, f64, &str>::with_metadata(1.5, "ring").at::<First>();
+
+    let json = serde_json::to_string(&modifier).unwrap();
+    let loaded: Flat<Dummy, f64, &str> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded, modifier);
+}
+
+#[test]
+#[cfg(all(feature = "refcell", feature = "sync"))]
+fn mini_stat() {
+    let stat = MiniStat::<Owned>::with_base(1.);
+    stat.apply_flat(Flat::with_metadata(1., 3));
+
+    let json = serde_json::to_string(&stat).unwrap();
+    let loaded: MiniStatSync<Owned> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded.cached(), 2.);
+    assert_eq!(loaded.stat_mut().flats_with_metadata(3).count(), 1);
+}
+
+#[test]
+fn unserializable() {
+    let mut stat = Stat::<Owned>::with_base(1.);
+    stat.set_max(Bound::from_fn(|| 10.));
+
+    assert!(serde_json::to_string(&stat).is_err());
+
+    stat.set_max(10.).set_flat_curve(Curve::hyperbolic(10.));
+
+    assert!(serde_json::to_string(&stat).is_err());
+
+    stat.clear_curves();
+    let buff = stat.apply_flat_if(Flat::from_raw(1.), |night: &bool| *night);
+
+    assert!(serde_json::to_string(&stat).is_err());
+
+    stat.remove_by_id(buff);
+
+    assert!(serde_json::to_string(&stat).is_ok());
+    assert!(serde_json::from_str::<ModifierId>(&u64::MAX.to_string()).is_err());
+}
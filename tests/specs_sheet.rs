@@ -0,0 +1,97 @@
+use mini_stat::{prelude::*, sheet::StatSheet};
+
+#[derive(Debug, Default)]
+struct Strength;
+
+impl StatMarker for Strength {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Agility;
+
+impl StatMarker for Agility {
+    type Raw = f64;
+
+    type Metadata = &'static str;
+
+    type Formula = Standard;
+}
+
+#[derive(Debug, Default)]
+struct Level;
+
+impl StatMarker for Level {
+    type Raw = f32;
+
+    type Metadata = ();
+
+    type Formula = Standard;
+}
+
+#[test]
+fn typed_access() {
+    let mut sheet = StatSheet::<2>::new();
+    sheet
+        .insert(Stat::<Strength>::with_base(1.))
+        .insert(Stat::<Agility>::with_base(2.));
+
+    assert_eq!(sheet.len(), 2);
+    assert!(sheet.contains::<Strength>());
+    assert!(!sheet.contains::<Level>());
+
+    sheet
+        .get_mut::<Strength>()
+        .unwrap()
+        .apply_flat(Flat::from_raw(2.));
+
+    assert_eq!(sheet.value::<Strength>(), Some(3.));
+    assert_eq!(sheet.get::<Agility>().unwrap().base(), 2.);
+    assert!(sheet.get::<Level>().is_none());
+
+    let strength = sheet.remove::<Strength>().unwrap();
+
+    assert_eq!(strength.cached(), Some(3.));
+    assert!(sheet.value::<Strength>().is_none());
+}
+
+#[test]
+fn apply_to_all() {
+    let mut sheet = StatSheet::<2>::new();
+    sheet
+        .insert(Stat::<Strength>::with_base(1.))
+        .insert(Stat::<Agility>::with_base(2.))
+        .insert(Stat::<Level>::with_base(3.));
+
+    let flats = sheet.apply_to_all(FlatAll::with_metadata(1., "blessing"));
+    let muls = sheet.apply_to_all(MultiplicativeAll::<f64, &str>::from_raw(2.));
+
+    assert_eq!(flats.len(), 2);
+    assert_eq!(muls.len(), 2);
+    assert_eq!(sheet.value::<Strength>(), Some(4.));
+    assert_eq!(sheet.value::<Agility>(), Some(6.));
+    assert_eq!(sheet.value::<Level>(), Some(3.));
+
+    for id in muls {
+        sheet.remove_by_id(id);
+    }
+
+    assert_eq!(sheet.value::<Strength>(), Some(2.));
+    assert_eq!(sheet.value::<Agility>(), Some(3.));
+
+    sheet.apply_to_all(AdditiveAll::<f32, ()>::from_raw(1.));
+
+    assert_eq!(sheet.value::<Level>(), Some(6.));
+    assert_eq!(
+        sheet
+            .get::<Agility>()
+            .unwrap()
+            .flats_with_metadata("blessing")
+            .count(),
+        1
+    );
+}
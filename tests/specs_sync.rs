@@ -1,4 +1,21 @@
-use mini_stat::{prelude::*, sync::MiniStat};
+#![cfg(feature = "sync")]
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use mini_stat::{
+    bound::Bound,
+    error::Error,
+    number::Fixed,
+    prelude::*,
+    sync::MiniStat,
+    timed::{Duration, Tick},
+};
 
 #[derive(Debug, Default)]
 struct Dummy;
@@ -7,6 +24,8 @@ impl StatMarker for Dummy {
     type Raw = f64;
 
     type Metadata = &'static str;
+
+    type Formula = Standard;
 }
 
 #[test]
@@ -14,10 +33,12 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(3.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_flat(Flat::from_raw(1.))
-        .apply_flat(Flat::from_raw(2.))
-        .apply_flat(Flat::from_raw(3.));
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_flat(Flat::from_raw(2.));
+        stat.apply_flat(Flat::from_raw(3.));
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -26,10 +47,12 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(6.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.1))
-        .apply_add(Additive::from_raw(0.3));
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_add(Additive::from_raw(0.1));
+        stat.apply_add(Additive::from_raw(0.1));
+        stat.apply_add(Additive::from_raw(0.3));
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -38,11 +61,13 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(1.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_mul(Multiplicative::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(3.))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_mul(Multiplicative::from_raw(0.5));
+        stat.apply_mul(Multiplicative::from_raw(3.));
+        stat.apply_mul(Multiplicative::from_raw(6.));
+        stat.cache_value();
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -51,11 +76,13 @@ fn specs() {
     let stat = MiniStat::<Dummy>::with_base(0.);
     println!("{:.0}", stat.cached());
 
-    stat.stat_mut()
-        .apply_flat(Flat::from_raw(1.))
-        .apply_add(Additive::from_raw(0.5))
-        .apply_mul(Multiplicative::from_raw(6.))
-        .cache_value();
+    {
+        let mut stat = stat.stat_mut();
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_add(Additive::from_raw(0.5));
+        stat.apply_mul(Multiplicative::from_raw(6.));
+        stat.cache_value();
+    }
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 
@@ -70,3 +97,323 @@ fn specs() {
     println!("{:.0}", stat.cached());
     println!("{stat:#?}");
 }
+
+#[test]
+fn remove_by_id() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+
+    let first = stat.apply_flat(Flat::from_raw(5.));
+    let second = stat.apply_flat(Flat::from_raw(5.));
+
+    assert_eq!(stat.cached(), 11.);
+
+    stat.remove_by_id(first);
+
+    assert_eq!(stat.cached(), 6.);
+    assert_eq!(stat.stat_mut().flats()[0].id(), Some(second));
+}
+
+#[test]
+fn timed() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+
+    let buff = stat.apply_flat_timed(Flat::from_raw(2.), Duration::Turns(2));
+
+    assert_eq!(stat.cached(), 3.);
+    assert!(stat.tick(Tick::Turns(1)).is_empty());
+    assert_eq!(stat.remaining(buff), Some(Duration::Turns(1)));
+    assert_eq!(stat.tick(Tick::Turns(1)).as_slice(), &[buff]);
+    assert_eq!(stat.cached(), 1.);
+}
+
+#[test]
+fn resource() {
+    let mana = MiniResourceSync::<Dummy>::with_max(50.);
+
+    assert!(mana.spend(20.));
+    assert_eq!(mana.current(), 30.);
+
+    mana.resource_mut()
+        .max_mut()
+        .apply_flat(Flat::from_raw(-40.));
+
+    assert_eq!(mana.max(), 10.);
+    assert_eq!(mana.current(), 10.);
+    assert_eq!(mana.damage(15.), 10.);
+    assert_eq!(mana.heal(5.), 5.);
+}
+
+#[test]
+fn observers() {
+    let (sender, receiver) = mpsc::channel();
+    let stat = MiniStat::<Dummy>::with_base(2.);
+
+    let observer = stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+    let buff = thread::scope(|scope| {
+        scope
+            .spawn(|| stat.apply_mul(Multiplicative::from_raw(3.)))
+            .join()
+            .unwrap()
+    });
+    stat.remove_by_id(buff);
+    stat.unsubscribe(observer);
+    stat.apply_flat(Flat::from_raw(1.));
+
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        [(2., 6.), (6., 2.)]
+    );
+}
+
+#[test]
+fn base() {
+    let stat = MiniStat::<Dummy>::with_base(4.);
+    stat.apply_flat(Flat::from_raw(1.));
+
+    stat.set_base(5.);
+    assert_eq!(stat.cached(), 6.);
+
+    stat.add_to_base(1.);
+    stat.map_base(|base| base * 2.);
+    assert_eq!(stat.base(), 12.);
+    assert_eq!(stat.cached(), 13.);
+}
+
+#[test]
+fn conditional() {
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    stat.apply_mul_if(Multiplicative::from_raw(3.), |night: &bool| *night);
+
+    assert!(stat.is_stale(&true));
+    assert_eq!(stat.evaluate_with(&true), 6.);
+    assert!(!stat.is_stale(&true));
+    assert_eq!(stat.evaluate_with(&false), 2.);
+    assert_eq!(stat.cached(), 2.);
+}
+
+#[test]
+fn concurrent_reads() {
+    let stat = MiniStat::<Dummy>::with_base(1.);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    assert!(stat.cached() >= 1.);
+                }
+            });
+        }
+        scope.spawn(|| {
+            for _ in 0..1000 {
+                let id = stat.apply_flat(Flat::from_raw(1.));
+                stat.remove_by_id(id);
+            }
+        });
+    });
+
+    assert_eq!(stat.cached(), 1.);
+    assert_eq!(stat.stat().cached(), Some(1.));
+}
+
+#[derive(Debug, Default)]
+struct Speed;
+
+impl StatMarker for Speed {
+    type Raw = Fixed;
+
+    type Metadata = ();
+
+    type Formula = Standard;
+}
+
+#[test]
+fn published_value() {
+    let stat = MiniStat::<Speed>::with_base(Fixed::from_int(2));
+    let generation = stat.generation();
+
+    stat.apply_mul(Multiplicative::from_raw(Fixed::from_ratio(3, 2)));
+    assert_eq!(stat.cached(), Fixed::from_int(3));
+    assert_eq!(stat.generation(), generation + 1);
+
+    stat.stat_mut().set_base(Fixed::from_int(4));
+    assert_eq!(stat.cached(), Fixed::from_int(6));
+    assert_eq!(stat.generation(), generation + 2);
+
+    // Releasing the lock without changing the final value doesn't count.
+    stat.apply_flat_if(Flat::from_raw(Fixed::from_int(1)), |_: &()| true);
+    stat.evaluate_with(&());
+    stat.tick(Tick::Turns(1));
+    stat.batch(|stat| {
+        stat.set_base(Fixed::from_int(0));
+        Err::<(), _>(())
+    })
+    .unwrap_err();
+    assert_eq!(stat.generation(), generation + 2);
+}
+
+#[test]
+fn dynamic_bounds_disable_publishing() {
+    let cap = Arc::new(AtomicU64::new(50));
+    let stat = MiniStat::<Dummy>::with_base(80.);
+    stat.stat_mut().set_max(Bound::from_fn({
+        let cap = cap.clone();
+        move || cap.load(Ordering::SeqCst) as f64
+    }));
+    let generation = stat.generation();
+
+    assert_eq!(stat.cached(), 50.);
+
+    cap.store(20, Ordering::SeqCst);
+
+    assert_eq!(stat.cached(), 20.);
+    assert_eq!(stat.try_cached(), Ok(20.));
+    assert_eq!(stat.cached(), stat.stat().cached().unwrap());
+    assert_eq!(stat.generation(), generation);
+
+    stat.stat_mut().set_max(60.);
+    assert_eq!(stat.cached(), 60.);
+    assert_eq!(stat.generation(), generation + 1);
+}
+
+#[test]
+fn no_stale_reads() {
+    let stat = MiniStat::<Dummy>::with_base(0.);
+    let written = AtomicU64::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| loop {
+                // Anything written before the read started must be visible.
+                let floor = written.load(Ordering::SeqCst);
+                let value = stat.cached();
+                assert!(
+                    value >= floor as f64,
+                    "read {value} after {floor} was written"
+                );
+                if floor == 10_000 {
+                    break;
+                }
+            });
+        }
+        scope.spawn(|| {
+            for base in 1..=10_000 {
+                stat.set_base(base as f64);
+                written.store(base, Ordering::SeqCst);
+            }
+        });
+    });
+
+    assert_eq!(stat.cached(), 10_000.);
+}
+
+#[test]
+fn poisoning() {
+    let stat = MiniStat::<Dummy>::with_base(2.);
+
+    thread::scope(|scope| {
+        let panicked = scope.spawn(|| {
+            let mut guard = stat.stat_mut();
+            guard.apply_flat(Flat::from_raw(1.));
+            panic!("poison the stat");
+        });
+        assert!(panicked.join().is_err());
+    });
+
+    assert_eq!(
+        stat.try_apply_flat(Flat::from_raw(1.)),
+        Err(Error::Poisoned)
+    );
+    assert!(stat.try_stat().is_err());
+    assert_eq!(stat.try_set_base(4.), Err(Error::Poisoned));
+    assert_eq!(stat.try_tick(Tick::Turns(1)), Err(Error::Poisoned));
+    assert_eq!(stat.try_batch(|_| Ok::<_, ()>(())), Err(Error::Poisoned));
+    // Nothing is published while unwinding, so the value from before the panic stays readable.
+    assert_eq!(stat.try_cached(), Ok(2.));
+
+    stat.clear_poison();
+    assert_eq!(stat.cached(), 3.);
+
+    let id = stat.try_apply_flat(Flat::from_raw(1.)).unwrap();
+    assert_eq!(stat.try_cached(), Ok(4.));
+    stat.try_remove_by_id(id).unwrap();
+    assert_eq!(stat.cached(), 3.);
+}
+
+#[test]
+fn reentrant_observer() {
+    let conflicts = Arc::new(AtomicU64::new(0));
+    let stat = Arc::new(MiniStat::<Dummy>::with_base(2.));
+
+    stat.subscribe({
+        let stat = Arc::downgrade(&stat);
+        let conflicts = conflicts.clone();
+        move |_, new| {
+            let stat = stat.upgrade().unwrap();
+            assert_eq!(stat.try_set_base(new * 2.), Err(Error::BorrowConflict));
+            assert_eq!(
+                stat.try_remove_with_metadata("buff"),
+                Err(Error::BorrowConflict)
+            );
+            assert_eq!(stat.try_cached(), Err(Error::BorrowConflict));
+            conflicts.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    stat.apply_flat(Flat::from_raw(1.));
+
+    assert_eq!(conflicts.load(Ordering::SeqCst), 1);
+    assert_eq!(stat.cached(), 3.);
+    assert_eq!(stat.try_cached(), Ok(3.));
+}
+
+#[test]
+fn batch() {
+    let (sender, receiver) = mpsc::channel();
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+    let generation = stat.generation();
+
+    stat.batch(|stat| {
+        stat.apply_flat(Flat::from_raw(1.));
+        stat.apply_mul(Multiplicative::from_raw(2.));
+        Ok::<_, ()>(())
+    })
+    .unwrap();
+
+    assert_eq!(stat.generation(), generation + 1);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(2., 6.)]);
+
+    let err = stat.batch(|stat| {
+        stat.set_base(0.);
+        Err::<(), _>(())
+    });
+
+    assert!(err.is_err());
+    assert_eq!(stat.cached(), 6.);
+    assert!(receiver.try_iter().next().is_none());
+}
+
+#[test]
+fn batch_panic() {
+    let (sender, receiver) = mpsc::channel();
+    let stat = MiniStat::<Dummy>::with_base(2.);
+    stat.subscribe(move |old, new| sender.send((old, new)).unwrap());
+
+    thread::scope(|scope| {
+        let panicked = scope.spawn(|| {
+            stat.batch(|stat| {
+                stat.apply_flat(Flat::from_raw(1.));
+                panic!("batch failed");
+                #[allow(unreachable_code)]
+                Ok::<_, ()>(())
+            })
+        });
+        assert!(panicked.join().is_err());
+    });
+
+    stat.clear_poison();
+    assert_eq!(stat.cached(), 2.);
+
+    stat.apply_flat(Flat::from_raw(10.));
+    assert_eq!(stat.cached(), 12.);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [(2., 12.)]);
+}